@@ -1,7 +1,15 @@
-/// Module implementing various algorithms for freelancer data analysis.
+//! Module implementing various algorithms for freelancer data analysis.
 
-use std::collections::VecDeque;
-use super::data_loader::Freelancer;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::error::Error;
+use ndarray::Array2;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rayon::prelude::*;
+use super::data_loader::{ExperienceLevel, Freelancer};
+#[cfg(test)]
+use super::data_loader::FreelancerBuilder;
 
 /// Finds connected components in a graph using Breadth-First Search (BFS).
 /// 
@@ -9,96 +17,1943 @@ use super::data_loader::Freelancer;
 /// 
 /// # Returns: `Vec<Vec<usize>>` - Vector of clusters, where each cluster is a vector of node indices
 pub fn find_connected_components(adj_list: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    components_iter(adj_list).collect()
+}
+
+/// Finds connected components with an iterative depth-first search.
+///
+/// Uses an explicit stack instead of recursion, so large components cannot overflow
+/// the call stack. Components are found from the lowest unvisited node, like the BFS
+/// version, and members are listed in DFS preorder, visiting neighbors in adjacency
+/// list order.
+///
+/// # Arguments: `adj_list` - Adjacency list representation of the graph
+///
+/// # Returns: `Vec<Vec<usize>>` - Clusters of node indices, ordered by their lowest node
+pub fn find_connected_components_dfs(adj_list: &[Vec<usize>]) -> Vec<Vec<usize>> {
     let mut visited = vec![false; adj_list.len()];
     let mut clusters = Vec::new();
 
-    for node in 0..adj_list.len() {
-        if !visited[node] {
-            let mut cluster = Vec::new();
-            let mut queue = VecDeque::new();
-            queue.push_back(node);
-            visited[node] = true;
+    for start in 0..adj_list.len() {
+        if visited[start] {
+            continue;
+        }
+        let mut cluster = Vec::new();
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            if visited[node] {
+                continue;
+            }
+            visited[node] = true;
+            cluster.push(node);
+            // Push in reverse so the first neighbor is explored first
+            stack.extend(adj_list[node].iter().rev().filter(|&&neighbor| !visited[neighbor]));
+        }
+        clusters.push(cluster);
+    }
+    clusters
+}
+
+/// Returns the cluster with the most members.
+///
+/// # Arguments: `clusters` - Clusters of node indices, e.g. from `find_connected_components`
+///
+/// # Returns: `Option<&Vec<usize>>` - The largest cluster, the earliest one on ties, or `None` if there are no clusters
+pub fn largest_component(clusters: &[Vec<usize>]) -> Option<&Vec<usize>> {
+    clusters.iter()
+        .enumerate()
+        .max_by(|a, b| a.1.len().cmp(&b.1.len()).then_with(|| b.0.cmp(&a.0)))
+        .map(|(_, cluster)| cluster)
+}
+
+/// Drops clusters with fewer than `min_size` members, keeping the rest in order.
+///
+/// # Arguments: `clusters` - Clusters of node indices, `min_size` - Smallest cluster to keep
+///
+/// # Returns: `Vec<Vec<usize>>` - The clusters with at least `min_size` members, unchanged
+pub fn filter_clusters_by_size(clusters: Vec<Vec<usize>>, min_size: usize) -> Vec<Vec<usize>> {
+    clusters.into_iter().filter(|cluster| cluster.len() >= min_size).collect()
+}
+
+/// Lazily yields connected components in the order BFS discovers them.
+/// 
+/// Each call to `next` runs one BFS from the lowest unvisited node, so stopping
+/// early skips the work for the remaining components.
+/// 
+/// # Arguments: `adj_list` - Adjacency list representation of the graph
+/// 
+/// # Returns: `ComponentsIter` - Iterator over clusters of node indices
+pub fn components_iter(adj_list: &[Vec<usize>]) -> ComponentsIter<'_> {
+    ComponentsIter {
+        adj_list,
+        visited: vec![false; adj_list.len()],
+        next_node: 0,
+    }
+}
+
+/// Iterator over the connected components of a graph, created by `components_iter`.
+pub struct ComponentsIter<'a> {
+    adj_list: &'a [Vec<usize>],
+    visited: Vec<bool>,
+    next_node: usize,
+}
+
+impl Iterator for ComponentsIter<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        // Skip nodes already assigned to an earlier cluster
+        while self.next_node < self.adj_list.len() && self.visited[self.next_node] {
+            self.next_node += 1;
+        }
+        let node = self.next_node;
+        if node >= self.adj_list.len() {
+            return None;
+        }
+
+        let mut cluster = Vec::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(node);
+        self.visited[node] = true;
+
+        while let Some(current) = queue.pop_front() {
+            cluster.push(current);
+            for &neighbor in &self.adj_list[current] {
+                if !self.visited[neighbor] {
+                    self.visited[neighbor] = true;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        Some(cluster)
+    }
+}
+
+/// Disjoint-set forest with path compression and union by rank.
+///
+/// Edges can be added with `union` as they are discovered, e.g. while the graph is
+/// being built, and `components` reads off the clusters at any point.
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+}
+
+impl UnionFind {
+    /// Creates `n` singleton sets.
+    pub fn new(n: usize) -> UnionFind {
+        UnionFind { parent: (0..n).collect(), rank: vec![0; n] }
+    }
+
+    /// Returns the representative of `x`'s set, compressing the path to it.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut node = x;
+        while self.parent[node] != root {
+            let next = self.parent[node];
+            self.parent[node] = root;
+            node = next;
+        }
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`; returns `false` if they were already joined.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a == root_b {
+            return false;
+        }
+        match self.rank[root_a].cmp(&self.rank[root_b]) {
+            std::cmp::Ordering::Less => self.parent[root_a] = root_b,
+            std::cmp::Ordering::Greater => self.parent[root_b] = root_a,
+            std::cmp::Ordering::Equal => {
+                self.parent[root_b] = root_a;
+                self.rank[root_a] += 1;
+            }
+        }
+        true
+    }
+
+    /// Groups all elements by set, ordered by each set's lowest element, members ascending.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut cluster_of_root: HashMap<usize, usize> = HashMap::new();
+        let mut clusters: Vec<Vec<usize>> = Vec::new();
+        for node in 0..self.parent.len() {
+            let root = self.find(node);
+            let cluster = *cluster_of_root.entry(root).or_insert_with(|| {
+                clusters.push(Vec::new());
+                clusters.len() - 1
+            });
+            clusters[cluster].push(node);
+        }
+        clusters
+    }
+}
+
+/// Finds connected components with a union-find instead of BFS.
+///
+/// Returns the same clusters as `find_connected_components`, though members are
+/// listed in ascending order rather than BFS order.
+///
+/// # Arguments: `adj_list` - Adjacency list representation of the graph
+///
+/// # Returns: `Vec<Vec<usize>>` - Clusters ordered by their lowest node index
+pub fn find_connected_components_union_find(adj_list: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut sets = UnionFind::new(adj_list.len());
+    for (node, neighbors) in adj_list.iter().enumerate() {
+        for &neighbor in neighbors {
+            sets.union(node, neighbor);
+        }
+    }
+    sets.components()
+}
+
+/// Finds the weakly connected components of a directed graph.
+///
+/// Edges are treated as undirected, so two nodes share a component when a path
+/// joins them ignoring edge direction. Union-find merges both endpoints of each
+/// edge regardless of which end lists it, so no symmetric copy is needed.
+///
+/// # Arguments: `adj_list` - Directed adjacency list, e.g. from `build_directed_graph`
+///
+/// # Returns: `Vec<Vec<usize>>` - Components in ascending order, ordered by their lowest node index
+pub fn weakly_connected_components(adj_list: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    find_connected_components_union_find(adj_list)
+}
+
+/// Finds the strongly connected components of a directed graph with Tarjan's algorithm.
+///
+/// Two nodes share a component when each can reach the other along edge directions.
+/// The depth-first search uses an explicit stack, like `cluster_critical_nodes`.
+///
+/// # Arguments: `adj_list` - Directed adjacency list, e.g. from `build_directed_graph`
+///
+/// # Returns: `Vec<Vec<usize>>` - Components in ascending order, ordered by their lowest node index
+pub fn strongly_connected_components(adj_list: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let n = adj_list.len();
+    let mut index: Vec<Option<usize>> = vec![None; n];
+    let mut lowlink = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut next_index = 0;
+    let mut components = Vec::new();
+
+    for start in 0..n {
+        if index[start].is_some() {
+            continue;
+        }
+        // Each frame is a node and the position of the next out-edge to explore
+        let mut frames = vec![(start, 0)];
+        index[start] = Some(next_index);
+        lowlink[start] = next_index;
+        next_index += 1;
+        stack.push(start);
+        on_stack[start] = true;
+
+        while let Some(frame) = frames.last_mut() {
+            let node = frame.0;
+            if let Some(&next) = adj_list[node].get(frame.1) {
+                frame.1 += 1;
+                match index[next] {
+                    None => {
+                        index[next] = Some(next_index);
+                        lowlink[next] = next_index;
+                        next_index += 1;
+                        stack.push(next);
+                        on_stack[next] = true;
+                        frames.push((next, 0));
+                    }
+                    Some(next_idx) if on_stack[next] => lowlink[node] = lowlink[node].min(next_idx),
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            frames.pop();
+            if let Some(&(parent, _)) = frames.last() {
+                lowlink[parent] = lowlink[parent].min(lowlink[node]);
+            }
+            if Some(lowlink[node]) == index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                component.sort_unstable();
+                components.push(component);
+            }
+        }
+    }
+    components.sort_unstable_by_key(|component| component[0]);
+    components
+}
+
+/// Similarity a pair must exceed to be connected by `build_collaboration_graph`.
+pub const DEFAULT_SIMILARITY_THRESHOLD: f32 = 0.7;
+
+/// Builds a collaboration graph based on shared attributes between freelancers.
+/// 
+/// # Arguments: `freelancers` - Slice of Freelancer structs to analyze
+/// 
+/// # Returns: `Vec<Vec<usize>>` - Adjacency list representation of the collaboration graph
+pub fn build_collaboration_graph(freelancers: &[Freelancer]) -> Vec<Vec<usize>> {
+    build_graph_with_threshold(freelancers, DEFAULT_SIMILARITY_THRESHOLD)
+}
+
+/// Builds a collaboration graph with a caller-chosen similarity cutoff.
+///
+/// Pairs are connected when their similarity is strictly greater than `threshold`,
+/// so 1.0 always gives a graph with no edges and lower values give denser graphs.
+/// Similarity scores lie in [0, 1], so thresholds outside that range are rejected.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs to analyze, `threshold` - Cutoff in [0, 1],
+/// e.g. `DEFAULT_SIMILARITY_THRESHOLD`
+///
+/// # Returns: `Result<Vec<Vec<usize>>, Box<dyn Error>>` - Adjacency list or error if the threshold is out of range
+pub fn build_collaboration_graph_with_threshold(
+    freelancers: &[Freelancer],
+    threshold: f32,
+) -> Result<Vec<Vec<usize>>, Box<dyn Error>> {
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(format!("similarity threshold must be in [0, 1], got {}", threshold).into());
+    }
+    Ok(build_graph_with_threshold(freelancers, threshold))
+}
+
+/// Default number of freelancers above which `build_collaboration_graph_checked` refuses to run.
+pub const DEFAULT_MAX_GRAPH_NODES: usize = 50_000;
+
+/// Builds the collaboration graph, refusing inputs too large for pairwise comparison.
+///
+/// Scoring is bucketed (see `build_weighted_graph_with_threshold`), so it costs about
+/// `b² / 2` comparisons for `b` distinct attribute tuples, but the adjacency list still
+/// holds one entry per edge, which approaches `n²` when large buckets match. Above
+/// `max_nodes` this returns an error instead of appearing to hang, unless `allow_large` is set.
+///
+/// # Arguments
+/// `freelancers` - Slice of Freelancer structs to analyze
+/// `max_nodes` - Largest input accepted without `allow_large`, e.g. `DEFAULT_MAX_GRAPH_NODES`
+/// `allow_large` - Skip the size check
+///
+/// # Returns: `Result<Vec<Vec<usize>>, Box<dyn Error>>` - Adjacency list or error if the input is too large
+pub fn build_collaboration_graph_checked(
+    freelancers: &[Freelancer],
+    max_nodes: usize,
+    allow_large: bool,
+) -> Result<Vec<Vec<usize>>, Box<dyn Error>> {
+    let n = freelancers.len();
+    if n > max_nodes && !allow_large {
+        let buckets = attribute_buckets(freelancers).len() as u128;
+        let max_edges = n as u128 * (n as u128 - 1) / 2;
+        return Err(format!(
+            "refusing to build a graph of {} freelancers (limit {}): scoring takes about {} comparisons \
+             across {} attribute buckets, but the graph can hold up to {} edges; sample the data, \
+             use the bucketed build_collaboration_graph_parallel, or pass allow_large = true",
+            n, max_nodes, buckets * (buckets + 1) / 2, buckets, max_edges
+        ).into());
+    }
+    Ok(build_collaboration_graph(freelancers))
+}
+
+/// Builds the collaboration graph keeping each edge's similarity score.
+///
+/// Uses the same threshold as `build_collaboration_graph`, whose adjacency list is
+/// this one with the weights dropped (see `to_unweighted`).
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs to analyze
+///
+/// # Returns: `Vec<Vec<(usize, f32)>>` - Weighted adjacency list of (neighbor, similarity) pairs
+pub fn build_weighted_collaboration_graph(freelancers: &[Freelancer]) -> Vec<Vec<(usize, f32)>> {
+    build_weighted_graph_with_threshold(freelancers, DEFAULT_SIMILARITY_THRESHOLD)
+}
+
+/// Drops the weights of a weighted adjacency list.
+///
+/// # Arguments: `weighted_adj` - Weighted adjacency list of (neighbor, weight) pairs
+///
+/// # Returns: `Vec<Vec<usize>>` - Adjacency list with the same neighbors in the same order
+pub fn to_unweighted(weighted_adj: &[Vec<(usize, f32)>]) -> Vec<Vec<usize>> {
+    weighted_adj.iter()
+        .map(|neighbors| neighbors.iter().map(|&(neighbor, _)| neighbor).collect())
+        .collect()
+}
+
+/// Builds a directed graph with an edge `i → j` wherever `predicate(i, j)` holds.
+///
+/// Unlike `build_collaboration_graph` the result need not be symmetric, so
+/// `find_connected_components` and its variants, which assume every edge is listed
+/// from both ends, do not apply; use `weakly_connected_components` or
+/// `strongly_connected_components` instead.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs, `predicate` - Whether the first freelancer
+/// has an edge to the second, e.g. `is_mentorship`
+///
+/// # Returns: `Vec<Vec<usize>>` - Out-neighbors of each node in ascending order; no self-loops
+pub fn build_directed_graph<F>(freelancers: &[Freelancer], predicate: F) -> Vec<Vec<usize>>
+where
+    F: Fn(&Freelancer, &Freelancer) -> bool,
+{
+    freelancers.iter()
+        .enumerate()
+        .map(|(i, from)| {
+            freelancers.iter()
+                .enumerate()
+                .filter(|&(j, to)| i != j && predicate(from, to))
+                .map(|(j, _)| j)
+                .collect()
+        })
+        .collect()
+}
+
+/// Mentorship edge predicate: an expert can mentor an entry-level freelancer on the same platform.
+///
+/// # Arguments: `mentor` - Candidate mentor, `mentee` - Candidate mentee
+///
+/// # Returns: `bool` - `true` if `mentor` is Expert, `mentee` is Entry level and they share a platform
+pub fn is_mentorship(mentor: &Freelancer, mentee: &Freelancer) -> bool {
+    mentor.platform == mentee.platform
+        && mentor.experience_level.parse() == Ok(ExperienceLevel::Expert)
+        && mentee.experience_level.parse() == Ok(ExperienceLevel::Entry)
+}
+
+/// Converts an adjacency list into a dense 0/1 adjacency matrix.
+///
+/// Entry `(i, j)` is 1.0 exactly when `j` is in `adj_list[i]`, so the undirected
+/// collaboration graph gives a symmetric matrix. Memory grows with `n²`.
+///
+/// # Arguments: `adj_list` - Adjacency list representation of the graph
+///
+/// # Returns: `Array2<f64>` - `n × n` adjacency matrix
+pub fn to_adjacency_matrix(adj_list: &[Vec<usize>]) -> Array2<f64> {
+    let mut matrix = Array2::zeros((adj_list.len(), adj_list.len()));
+    for (node, neighbors) in adj_list.iter().enumerate() {
+        for &neighbor in neighbors {
+            matrix[[node, neighbor]] = 1.0;
+        }
+    }
+    matrix
+}
+
+/// Converts a weighted adjacency list into a dense matrix of edge weights.
+///
+/// # Arguments: `weighted_adj` - Weighted adjacency list of (neighbor, weight) pairs
+///
+/// # Returns: `Array2<f64>` - `n × n` matrix with each edge's weight and 0.0 where there is no edge
+pub fn to_weighted_adjacency_matrix(weighted_adj: &[Vec<(usize, f32)>]) -> Array2<f64> {
+    let mut matrix = Array2::zeros((weighted_adj.len(), weighted_adj.len()));
+    for (node, neighbors) in weighted_adj.iter().enumerate() {
+        for &(neighbor, weight) in neighbors {
+            matrix[[node, neighbor]] = weight as f64;
+        }
+    }
+    matrix
+}
+
+/// Builds the collaboration graph, connecting pairs whose similarity exceeds `threshold`.
+fn build_graph_with_threshold(freelancers: &[Freelancer], threshold: f32) -> Vec<Vec<usize>> {
+    to_unweighted(&build_weighted_graph_with_threshold(freelancers, threshold))
+}
+
+/// Builds the weighted collaboration graph, connecting pairs whose similarity exceeds `threshold`.
+///
+/// `shared_attributes` only looks at the four categorical fields, so freelancers with
+/// the same (category, platform, region, experience) tuple score identically against
+/// everyone. They are grouped into buckets and the score is computed once per pair of
+/// buckets, which costs `O(b²)` comparisons for `b` distinct tuples instead of `O(n²)`.
+/// Neighbors are listed in ascending index order.
+fn build_weighted_graph_with_threshold(freelancers: &[Freelancer], threshold: f32) -> Vec<Vec<(usize, f32)>> {
+    let buckets = attribute_buckets(freelancers);
+    let mut adj_list = vec![Vec::new(); freelancers.len()];
+    for (a, members_a) in buckets.iter().enumerate() {
+        for (b, members_b) in buckets.iter().enumerate().skip(a) {
+            let score = shared_attributes(&freelancers[members_a[0]], &freelancers[members_b[0]]);
+            if score <= threshold {
+                continue;
+            }
+            for &i in members_a {
+                for &j in members_b {
+                    if a != b || i < j {
+                        adj_list[i].push((j, score));
+                        adj_list[j].push((i, score));
+                    }
+                }
+            }
+        }
+    }
+    for neighbors in &mut adj_list {
+        neighbors.sort_unstable_by_key(|&(neighbor, _)| neighbor);
+    }
+    adj_list
+}
+
+/// Groups freelancers by their (category, platform, region, experience) tuple.
+///
+/// Buckets are numbered in order of their first member and list members in ascending order.
+fn attribute_buckets(freelancers: &[Freelancer]) -> Vec<Vec<usize>> {
+    let mut bucket_index: HashMap<(&str, &str, &str, &str), usize> = HashMap::new();
+    let mut buckets: Vec<Vec<usize>> = Vec::new();
+    for (i, f) in freelancers.iter().enumerate() {
+        let key = (f.job_category.as_str(), f.platform.as_str(), f.client_region.as_str(), f.experience_level.as_str());
+        let bucket = *bucket_index.entry(key).or_insert_with(|| {
+            buckets.push(Vec::new());
+            buckets.len() - 1
+        });
+        buckets[bucket].push(i);
+    }
+    buckets
+}
+
+/// Builds the same graph as `build_collaboration_graph`, spreading the work across threads with rayon.
+///
+/// Each attribute bucket (see `build_weighted_graph_with_threshold`) is scored against
+/// every other in parallel and collects the sorted members of the buckets it connects
+/// to. A freelancer's neighbor list is its bucket's list without itself, so the
+/// result is identical to the sequential version.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs to analyze
+///
+/// # Returns: `Vec<Vec<usize>>` - Adjacency list representation of the collaboration graph
+pub fn build_collaboration_graph_parallel(freelancers: &[Freelancer]) -> Vec<Vec<usize>> {
+    let buckets = attribute_buckets(freelancers);
+    let mut bucket_of = vec![0; freelancers.len()];
+    for (bucket, members) in buckets.iter().enumerate() {
+        for &member in members {
+            bucket_of[member] = bucket;
+        }
+    }
+
+    // Members of every bucket connected to each bucket, including its own when it matches itself
+    let bucket_neighbors: Vec<Vec<usize>> = (0..buckets.len())
+        .into_par_iter()
+        .map(|a| {
+            let mut members: Vec<usize> = buckets
+                .iter()
+                .filter(|members_b| shared_attributes(&freelancers[buckets[a][0]], &freelancers[members_b[0]]) > DEFAULT_SIMILARITY_THRESHOLD)
+                .flat_map(|members_b| members_b.iter().copied())
+                .collect();
+            members.sort_unstable();
+            members
+        })
+        .collect();
+
+    (0..freelancers.len())
+        .into_par_iter()
+        .map(|i| bucket_neighbors[bucket_of[i]].iter().copied().filter(|&j| j != i).collect())
+        .collect()
+}
+
+/// Reference implementation of `build_graph_with_threshold` comparing every pair directly.
+#[cfg(test)]
+fn build_graph_naive(freelancers: &[Freelancer], threshold: f32) -> Vec<Vec<usize>> {
+    let n = freelancers.len();
+    let mut adj_list = vec![Vec::new(); n];
+
+    for i in 0..n {
+        for j in (i + 1)..n {
+            if shared_attributes(&freelancers[i], &freelancers[j]) > threshold {
+                adj_list[i].push(j);
+                adj_list[j].push(i);
+            }
+        }
+    }
+    adj_list
+}
+
+/// Finds the freelancers whose removal splits their cluster into more components.
+///
+/// These are the articulation points of the graph, found with Tarjan's low-link DFS:
+/// a non-root node is critical if some DFS child cannot reach above it without passing
+/// through it, and a DFS root is critical if it has two or more children. The DFS uses
+/// an explicit stack so large clusters do not overflow the call stack.
+///
+/// # Arguments: `adj_list` - Adjacency list representation of the graph
+///
+/// # Returns: `Vec<usize>` - Indices of the articulation points in ascending order
+pub fn cluster_critical_nodes(adj_list: &[Vec<usize>]) -> Vec<usize> {
+    let n = adj_list.len();
+    let mut discovery = vec![usize::MAX; n];
+    let mut low = vec![0; n];
+    let mut critical = vec![false; n];
+    let mut time = 0;
+
+    for root in 0..n {
+        if discovery[root] != usize::MAX {
+            continue;
+        }
+        discovery[root] = time;
+        low[root] = time;
+        time += 1;
+        let mut root_children = 0;
+        // Each frame is (node, parent, position of the next neighbor to visit)
+        let mut stack = vec![(root, usize::MAX, 0)];
+
+        while let Some(frame) = stack.last_mut() {
+            let (node, parent, next) = *frame;
+            if let Some(&neighbor) = adj_list[node].get(next) {
+                frame.2 += 1;
+                if neighbor == parent {
+                    continue;
+                }
+                if discovery[neighbor] == usize::MAX {
+                    discovery[neighbor] = time;
+                    low[neighbor] = time;
+                    time += 1;
+                    if node == root {
+                        root_children += 1;
+                    }
+                    stack.push((neighbor, node, 0));
+                } else {
+                    low[node] = low[node].min(discovery[neighbor]);
+                }
+            } else {
+                stack.pop();
+                if parent != usize::MAX {
+                    low[parent] = low[parent].min(low[node]);
+                    if parent != root && low[node] >= discovery[parent] {
+                        critical[parent] = true;
+                    }
+                }
+            }
+        }
+        critical[root] = root_children >= 2;
+    }
+
+    (0..n).filter(|&node| critical[node]).collect()
+}
+
+/// Computes the Newman modularity of a partition of an undirected graph.
+///
+/// `Q = Σ_c [ L_c / m - (d_c / 2m)² ]`, where `m` is the number of edges, `L_c` the
+/// number of edges inside cluster `c` and `d_c` the total degree of its members.
+///
+/// # Arguments: `adj_list` - Adjacency list of the graph, `clusters` - Partition of the nodes
+///
+/// # Returns: `f64` - Modularity in [-0.5, 1.0], or 0.0 for a graph without edges
+pub fn modularity(adj_list: &[Vec<usize>], clusters: &[Vec<usize>]) -> f64 {
+    let total_degree: usize = adj_list.iter().map(|neighbors| neighbors.len()).sum();
+    if total_degree == 0 {
+        return 0.0;
+    }
+    let m = total_degree as f64 / 2.0;
+    let labels = cluster_labels(clusters, adj_list.len());
+
+    let mut q = 0.0;
+    for members in clusters {
+        let mut internal_endpoints = 0;
+        let mut degree_sum = 0;
+        for &node in members {
+            degree_sum += adj_list[node].len();
+            internal_endpoints += adj_list[node].iter()
+                .filter(|&&neighbor| labels[neighbor] == labels[node])
+                .count();
+        }
+        // Each internal edge is seen from both endpoints
+        let internal_edges = internal_endpoints as f64 / 2.0;
+        q += internal_edges / m - (degree_sum as f64 / (2.0 * m)).powi(2);
+    }
+    q
+}
+
+/// Picks the similarity threshold whose connected components have the highest modularity.
+///
+/// For each candidate the collaboration graph is rebuilt, its connected components are
+/// found and their modularity is computed. Ties keep the earliest candidate.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs, `candidates` - Thresholds to try
+///
+/// # Returns: `(f32, f64)` - Best threshold and its modularity, or `(NaN, NaN)` if `candidates` is empty
+pub fn best_threshold_by_modularity(freelancers: &[Freelancer], candidates: &[f32]) -> (f32, f64) {
+    let mut best = (f32::NAN, f64::NAN);
+    for &threshold in candidates {
+        let adj_list = build_graph_with_threshold(freelancers, threshold);
+        let clusters = find_connected_components(&adj_list);
+        let q = modularity(&adj_list, &clusters);
+        if best.1.is_nan() || q > best.1 {
+            best = (threshold, q);
+        }
+    }
+    best
+}
+
+/// Suggests a similarity threshold at the knee of the pairwise score distribution.
+///
+/// All pairwise scores are sorted ascending and rescaled so index and score both run
+/// over [0, 1]; the knee is the point farthest from the chord joining the first and
+/// last scores. The result is halfway between the knee score and the next larger one,
+/// so the strict `>` cut of the graph builder falls in the gap between them.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs
+///
+/// # Returns: `f32` - Suggested threshold, or `DEFAULT_SIMILARITY_THRESHOLD` with fewer than three pairs or no spread
+pub fn similarity_knee(freelancers: &[Freelancer]) -> f32 {
+    let n = freelancers.len();
+    let mut scores = Vec::with_capacity(n * n.saturating_sub(1) / 2);
+    for i in 0..n {
+        for j in (i + 1)..n {
+            scores.push(shared_attributes(&freelancers[i], &freelancers[j]));
+        }
+    }
+    scores.sort_by(|a, b| a.total_cmp(b));
+
+    let (low, high) = match (scores.first(), scores.last()) {
+        (Some(&low), Some(&high)) if scores.len() >= 3 && high > low => (low, high),
+        _ => return DEFAULT_SIMILARITY_THRESHOLD,
+    };
+    let last = (scores.len() - 1) as f32;
+    // The chord runs from (0, 0) to (1, 1) after rescaling, so the distance to it is |x - y| / √2
+    let knee = (0..scores.len())
+        .max_by(|&a, &b| {
+            let distance = |i: usize| (i as f32 / last - (scores[i] - low) / (high - low)).abs();
+            distance(a).total_cmp(&distance(b))
+        })
+        .unwrap_or(0);
+    let next = scores[knee..].iter().copied().find(|&s| s > scores[knee]).unwrap_or(scores[knee]);
+    (scores[knee] + next) / 2.0
+}
+
+/// Computes the modularity of a partition of a weighted undirected graph.
+///
+/// `Q = Σ_c [ Σ_in(c) / 2m - (Σ_tot(c) / 2m)² ]`, where `2m` is the sum of all
+/// adjacency weights, `Σ_in(c)` sums the weights of adjacency entries with both ends
+/// in `c` and `Σ_tot(c)` the weighted degrees of its members. With unit weights this
+/// equals `modularity`.
+///
+/// # Arguments: `weighted_adj` - Weighted adjacency list listing each edge from both ends,
+/// `partition` - Community label of every node
+///
+/// # Returns: `f64` - Modularity, or 0.0 for a graph without edges
+pub fn weighted_modularity(weighted_adj: &[Vec<(usize, f32)>], partition: &[usize]) -> f64 {
+    let two_m = total_adjacency_weight(weighted_adj);
+    if two_m == 0.0 {
+        return 0.0;
+    }
+
+    let mut internal: HashMap<usize, f64> = HashMap::new();
+    let mut total: HashMap<usize, f64> = HashMap::new();
+    for (node, neighbors) in weighted_adj.iter().enumerate() {
+        for &(neighbor, weight) in neighbors {
+            *total.entry(partition[node]).or_insert(0.0) += weight as f64;
+            if partition[neighbor] == partition[node] {
+                *internal.entry(partition[node]).or_insert(0.0) += weight as f64;
+            }
+        }
+    }
+
+    total.iter()
+        .map(|(community, &tot)| internal.get(community).copied().unwrap_or(0.0) / two_m - (tot / two_m).powi(2))
+        .sum()
+}
+
+/// Computes the change in weighted modularity from moving one node to another community.
+///
+/// This is the local move evaluated by the Louvain method. Only the source and
+/// target communities change, so the gain is found from their internal and total
+/// weights before and after the move, in time linear in the graph size.
+///
+/// # Arguments: `weighted_adj` - Weighted adjacency list listing each edge from both ends,
+/// `partition` - Community label of every node, `node` - Node to move, `target_community` - Label to move it to
+///
+/// # Returns: `f64` - `Q(after) - Q(before)`; 0.0 if the node is already in the target or the graph has no edges
+pub fn modularity_gain(weighted_adj: &[Vec<(usize, f32)>], partition: &[usize], node: usize, target_community: usize) -> f64 {
+    let source_community = partition[node];
+    let two_m = total_adjacency_weight(weighted_adj);
+    if source_community == target_community || two_m == 0.0 {
+        return 0.0;
+    }
+
+    // Internal weight and total degree of the source and target communities
+    let (mut source_in, mut source_tot, mut target_in, mut target_tot) = (0.0, 0.0, 0.0, 0.0);
+    for (i, neighbors) in weighted_adj.iter().enumerate() {
+        let degree: f64 = neighbors.iter().map(|&(_, w)| w as f64).sum();
+        let internal: f64 = neighbors.iter()
+            .filter(|&&(j, _)| partition[j] == partition[i])
+            .map(|&(_, w)| w as f64)
+            .sum();
+        if partition[i] == source_community {
+            source_tot += degree;
+            source_in += internal;
+        } else if partition[i] == target_community {
+            target_tot += degree;
+            target_in += internal;
+        }
+    }
+
+    // Weight from the node to each community, with self-loops kept apart
+    let mut to_source = 0.0;
+    let mut to_target = 0.0;
+    let mut self_loop = 0.0;
+    for &(neighbor, weight) in &weighted_adj[node] {
+        let weight = weight as f64;
+        if neighbor == node {
+            self_loop += weight;
+        } else if partition[neighbor] == source_community {
+            to_source += weight;
+        } else if partition[neighbor] == target_community {
+            to_target += weight;
+        }
+    }
+    let degree: f64 = weighted_adj[node].iter().map(|&(_, w)| w as f64).sum();
+
+    let term = |internal: f64, tot: f64| internal / two_m - (tot / two_m).powi(2);
+    let before = term(source_in, source_tot) + term(target_in, target_tot);
+    let after = term(source_in - 2.0 * to_source - self_loop, source_tot - degree)
+        + term(target_in + 2.0 * to_target + self_loop, target_tot + degree);
+    after - before
+}
+
+/// Sums every adjacency weight, which is twice the total edge weight.
+fn total_adjacency_weight(weighted_adj: &[Vec<(usize, f32)>]) -> f64 {
+    weighted_adj.iter().flatten().map(|&(_, w)| w as f64).sum()
+}
+
+/// Finds communities by greedily maximizing weighted modularity with the Louvain method.
+///
+/// Each pass moves single nodes into the neighboring community with the largest
+/// modularity gain (see `modularity_gain`) until no move helps, then collapses each
+/// community into one node and repeats on the smaller graph. Unlike connected
+/// components, two dense groups joined by a few weak edges stay apart. Nodes are
+/// visited in an order shuffled by `seed`, so the same seed always gives the same
+/// communities.
+///
+/// # Arguments: `weighted_adj` - Weighted adjacency list listing each edge from both ends,
+/// `seed` - Random seed for the node visiting order
+///
+/// # Returns: `Vec<Vec<usize>>` - Communities in ascending order, ordered by their lowest node index
+pub fn louvain_communities(weighted_adj: &[Vec<(usize, f32)>], seed: u64) -> Vec<Vec<usize>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut graph: Vec<Vec<(usize, f64)>> = weighted_adj.iter()
+        .map(|neighbors| neighbors.iter().map(|&(j, w)| (j, w as f64)).collect())
+        .collect();
+    // Community of every original node in the current, possibly collapsed, graph
+    let mut membership: Vec<usize> = (0..weighted_adj.len()).collect();
+
+    loop {
+        let (labels, community_count) = louvain_local_moves(&graph, &mut rng);
+        if community_count == graph.len() {
+            break;
+        }
+        for community in membership.iter_mut() {
+            *community = labels[*community];
+        }
+
+        // Collapse each community into one node; internal weight becomes a self-loop
+        let mut collapsed: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); community_count];
+        for (node, neighbors) in graph.iter().enumerate() {
+            for &(neighbor, weight) in neighbors {
+                *collapsed[labels[node]].entry(labels[neighbor]).or_insert(0.0) += weight;
+            }
+        }
+        graph = collapsed.into_iter().map(|links| links.into_iter().collect()).collect();
+    }
+
+    let mut communities: Vec<Vec<usize>> = Vec::new();
+    let mut position: HashMap<usize, usize> = HashMap::new();
+    for (node, &community) in membership.iter().enumerate() {
+        let index = *position.entry(community).or_insert_with(|| {
+            communities.push(Vec::new());
+            communities.len() - 1
+        });
+        communities[index].push(node);
+    }
+    communities
+}
+
+/// Runs the Louvain local-move phase on one level of the graph.
+///
+/// Returns each node's community, relabelled `0..count` by first appearance, and the count.
+fn louvain_local_moves(graph: &[Vec<(usize, f64)>], rng: &mut StdRng) -> (Vec<usize>, usize) {
+    let n = graph.len();
+    let degree: Vec<f64> = graph.iter().map(|neighbors| neighbors.iter().map(|&(_, w)| w).sum()).collect();
+    let two_m: f64 = degree.iter().sum();
+    let mut community: Vec<usize> = (0..n).collect();
+
+    if two_m > 0.0 {
+        // Total degree of each community
+        let mut total = degree.clone();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.shuffle(rng);
+
+        let mut moved = true;
+        while moved {
+            moved = false;
+            for &node in &order {
+                let current = community[node];
+                let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+                for &(neighbor, weight) in &graph[node] {
+                    if neighbor != node {
+                        *links.entry(community[neighbor]).or_insert(0.0) += weight;
+                    }
+                }
+
+                // Gain of joining a community is proportional to k_i,c - Σ_tot(c) · k_i / 2m
+                total[current] -= degree[node];
+                let gain = |c: usize| links.get(&c).copied().unwrap_or(0.0) - total[c] * degree[node] / two_m;
+                let mut best = current;
+                let mut best_gain = gain(current);
+                for &candidate in links.keys() {
+                    let candidate_gain = gain(candidate);
+                    if candidate_gain > best_gain + 1e-12 {
+                        best = candidate;
+                        best_gain = candidate_gain;
+                    }
+                }
+                total[best] += degree[node];
+                if best != current {
+                    community[node] = best;
+                    moved = true;
+                }
+            }
+        }
+    }
+
+    let mut relabel: HashMap<usize, usize> = HashMap::new();
+    let labels: Vec<usize> = community.iter()
+        .map(|&c| {
+            let next = relabel.len();
+            *relabel.entry(c).or_insert(next)
+        })
+        .collect();
+    (labels, relabel.len())
+}
+
+/// Converts a list of clusters into a cluster label per node.
+///
+/// # Arguments: `clusters` - Clusters of node indices, `node_count` - Number of nodes in the graph
+///
+/// # Returns: `Vec<usize>` - Index of each node's cluster; nodes in no cluster get `usize::MAX`
+pub fn cluster_labels(clusters: &[Vec<usize>], node_count: usize) -> Vec<usize> {
+    let mut labels = vec![usize::MAX; node_count];
+    for (cluster_id, members) in clusters.iter().enumerate() {
+        for &node in members {
+            labels[node] = cluster_id;
+        }
+    }
+    labels
+}
+
+/// Counts the edges that stay inside clusters and the edges that cross between them.
+///
+/// Each undirected edge is counted once. Connected components never share an edge,
+/// so their inter-cluster count is always zero.
+///
+/// # Arguments: `adj_list` - Adjacency list of an undirected graph, `labels` - Cluster label of every node
+///
+/// # Returns: `(usize, usize)` - (intra-cluster edge count, inter-cluster edge count)
+pub fn intra_vs_inter_edges(adj_list: &[Vec<usize>], labels: &[usize]) -> (usize, usize) {
+    let mut intra = 0;
+    let mut inter = 0;
+    for (i, neighbors) in adj_list.iter().enumerate() {
+        for &j in neighbors.iter().filter(|&&j| j > i) {
+            if labels[i] == labels[j] {
+                intra += 1;
+            } else {
+                inter += 1;
+            }
+        }
+    }
+    (intra, inter)
+}
+
+/// Computes the normalized degree centrality of every node.
+///
+/// A node's centrality is its degree divided by `n - 1`, the most neighbors it
+/// could have, so a node connected to everyone scores 1.0.
+///
+/// # Arguments: `adj_list` - Adjacency list representation of the graph
+///
+/// # Returns: `Vec<f64>` - Centrality per node; 0.0 for every node of a single-node graph
+pub fn degree_centrality(adj_list: &[Vec<usize>]) -> Vec<f64> {
+    let n = adj_list.len();
+    if n < 2 {
+        return vec![0.0; n];
+    }
+    adj_list.iter()
+        .map(|neighbors| neighbors.len() as f64 / (n - 1) as f64)
+        .collect()
+}
+
+/// Finds the k-core of a graph: the nodes left after repeatedly removing every node with fewer than `k` neighbors.
+///
+/// Removing a node lowers its neighbors' degrees, which can push them below `k`
+/// in turn, so peripheral chains are stripped away until only nodes with at least
+/// `k` remaining neighbors are left.
+///
+/// # Arguments: `adj_list` - Adjacency list listing each edge from both ends, `k` - Minimum degree
+///
+/// # Returns: `Vec<usize>` - Nodes of the k-core in ascending order; every node when `k` is 0
+pub fn k_core(adj_list: &[Vec<usize>], k: usize) -> Vec<usize> {
+    let mut degree: Vec<usize> = adj_list.iter().map(Vec::len).collect();
+    let mut removed = vec![false; adj_list.len()];
+    let mut queue: VecDeque<usize> = (0..adj_list.len()).filter(|&node| degree[node] < k).collect();
+    for &node in &queue {
+        removed[node] = true;
+    }
+
+    while let Some(node) = queue.pop_front() {
+        for &neighbor in &adj_list[node] {
+            if removed[neighbor] {
+                continue;
+            }
+            degree[neighbor] -= 1;
+            if degree[neighbor] < k {
+                removed[neighbor] = true;
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    (0..adj_list.len()).filter(|&node| !removed[node]).collect()
+}
+
+/// Finds a shortest path between two freelancers with breadth-first search.
+///
+/// Every edge counts as one hop, so the path has the fewest collaborations between
+/// `start` and `goal`. Ties are broken by adjacency list order.
+///
+/// # Arguments: `adj_list` - Adjacency list representation of the graph, `start` - First node, `goal` - Last node
+///
+/// # Returns: `Option<Vec<usize>>` - Nodes from `start` to `goal` inclusive, so `len() - 1` hops; `[start]`
+/// when they are equal, or `None` if they are disconnected or either index is out of range
+pub fn shortest_path(adj_list: &[Vec<usize>], start: usize, goal: usize) -> Option<Vec<usize>> {
+    if start >= adj_list.len() || goal >= adj_list.len() {
+        return None;
+    }
+    let mut previous: Vec<Option<usize>> = vec![None; adj_list.len()];
+    let mut visited = vec![false; adj_list.len()];
+    let mut queue = VecDeque::from([start]);
+    visited[start] = true;
+
+    while let Some(node) = queue.pop_front() {
+        if node == goal {
+            let mut path = vec![goal];
+            while let Some(prev) = previous[*path.last()?] {
+                path.push(prev);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for &neighbor in &adj_list[node] {
+            if !visited[neighbor] {
+                visited[neighbor] = true;
+                previous[neighbor] = Some(node);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    None
+}
+
+/// Computes the local and average clustering coefficient of a graph.
+///
+/// The local coefficient of a node is the fraction of pairs of its neighbors that
+/// are themselves connected. Nodes with fewer than two neighbors get 0.0.
+///
+/// # Arguments: `adj_list` - Adjacency list representation of the graph
+///
+/// # Returns: `(f64, Vec<f64>)` - Average coefficient over all nodes and the per-node values
+pub fn clustering_coefficient(adj_list: &[Vec<usize>]) -> (f64, Vec<f64>) {
+    let n = adj_list.len();
+    let mut is_neighbor = vec![false; n];
+    let mut local = Vec::with_capacity(n);
+
+    for neighbors in adj_list {
+        let degree = neighbors.len();
+        if degree < 2 {
+            local.push(0.0);
+            continue;
+        }
+        for &neighbor in neighbors {
+            is_neighbor[neighbor] = true;
+        }
+        // Every link between two neighbors is counted once from each side
+        let mut links = 0;
+        for &neighbor in neighbors {
+            links += adj_list[neighbor].iter().filter(|&&other| is_neighbor[other]).count();
+        }
+        for &neighbor in neighbors {
+            is_neighbor[neighbor] = false;
+        }
+        let possible = degree * (degree - 1);
+        local.push(links as f64 / possible as f64);
+    }
+
+    let average = if n > 0 { local.iter().sum::<f64>() / n as f64 } else { 0.0 };
+    (average, local)
+}
+
+/// Estimates the natural number of clusters with the eigengap heuristic.
+///
+/// Builds the normalized Laplacian `L = I - D^-1/2 A D^-1/2`, computes its
+/// eigenvalues in ascending order and returns the `k` (1 ≤ k ≤ max_k) with the
+/// largest gap between the k-th and (k+1)-th eigenvalue. Isolated nodes get a zero
+/// row, so each one counts as its own cluster.
+///
+/// The eigenvalues come from the cyclic Jacobi method implemented in this module
+/// rather than an external linear algebra crate. It costs O(n³) per sweep, so this
+/// is meant for graphs of at most a few hundred nodes.
+///
+/// # Arguments: `adj_list` - Adjacency list of an undirected graph, `max_k` - Largest cluster count to consider
+///
+/// # Returns: `usize` - Estimated number of clusters (0 for an empty graph)
+pub fn estimate_clusters_eigengap(adj_list: &[Vec<usize>], max_k: usize) -> usize {
+    let n = adj_list.len();
+    if n <= 1 {
+        return n;
+    }
+
+    let degrees: Vec<f64> = adj_list.iter().map(|neighbors| neighbors.len() as f64).collect();
+    let mut laplacian = vec![vec![0.0; n]; n];
+    for (i, neighbors) in adj_list.iter().enumerate() {
+        if degrees[i] > 0.0 {
+            laplacian[i][i] = 1.0;
+        }
+        for &j in neighbors {
+            laplacian[i][j] = -1.0 / (degrees[i] * degrees[j]).sqrt();
+        }
+    }
+
+    let eigenvalues = symmetric_eigenvalues(laplacian);
+    let max_k = max_k.clamp(1, n - 1);
+    (1..=max_k)
+        .max_by(|&a, &b| {
+            let gap_a = eigenvalues[a] - eigenvalues[a - 1];
+            let gap_b = eigenvalues[b] - eigenvalues[b - 1];
+            // Prefer the smaller k when gaps tie
+            gap_a.total_cmp(&gap_b).then(b.cmp(&a))
+        })
+        .unwrap_or(1)
+}
+
+/// Computes the eigenvalues of a symmetric matrix with the cyclic Jacobi method.
+///
+/// # Returns: `Vec<f64>` - Eigenvalues sorted in ascending order
+fn symmetric_eigenvalues(mut a: Vec<Vec<f64>>) -> Vec<f64> {
+    let n = a.len();
+    for _sweep in 0..100 {
+        let off_diagonal: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j] * a[i][j])
+            .sum();
+        if off_diagonal < 1e-20 {
+            break;
+        }
+
+        for p in 0..n {
+            for q in (p + 1)..n {
+                if a[p][q].abs() < 1e-15 {
+                    continue;
+                }
+                // Rotation angle that zeroes a[p][q]
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let t = if theta == 0.0 { 1.0 } else { t };
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (head, tail) = a.split_at_mut(q);
+                for (apk, aqk) in head[p].iter_mut().zip(tail[0].iter_mut()) {
+                    let (vp, vq) = (*apk, *aqk);
+                    *apk = c * vp - s * vq;
+                    *aqk = s * vp + c * vq;
+                }
+            }
+        }
+    }
+
+    let mut eigenvalues: Vec<f64> = (0..n).map(|i| a[i][i]).collect();
+    eigenvalues.sort_by(|x, y| x.total_cmp(y));
+    eigenvalues
+}
+
+/// Calculates the fraction of graph edges that join freelancers of different job categories.
+///
+/// Each undirected edge is counted once. A high value means the similarity metric
+/// links freelancers across categories rather than only within them.
+///
+/// # Arguments: `adj_list` - Adjacency list of the graph, `freelancers` - Freelancers indexed like the graph
+///
+/// # Returns: `f64` - Share of edges crossing a category boundary (0.0 for a graph without edges)
+pub fn cross_category_edge_fraction(adj_list: &[Vec<usize>], freelancers: &[Freelancer]) -> f64 {
+    let mut total = 0usize;
+    let mut crossing = 0usize;
+
+    for (i, neighbors) in adj_list.iter().enumerate() {
+        for &j in neighbors.iter().filter(|&&j| j > i) {
+            total += 1;
+            if freelancers[i].job_category != freelancers[j].job_category {
+                crossing += 1;
+            }
+        }
+    }
+
+    if total == 0 {
+        return 0.0;
+    }
+    crossing as f64 / total as f64
+}
+
+/// Computes the categorical assortativity coefficient of an attribute over graph edges.
+///
+/// Uses Newman's definition `r = (Σ_i e_ii - Σ_i a_i²) / (1 - Σ_i a_i²)`, where
+/// `e_ij` is the fraction of edge ends joining value `i` to value `j` and `a_i` the
+/// fraction of edge ends at value `i`. A value near 1 means nodes link to their own
+/// kind (homophily), 0 means no preference and negative values mean mixing.
+///
+/// # Arguments: `adj_list` - Adjacency list of an undirected graph, `freelancers` - Freelancers indexed like the graph,
+/// `accessor` - Attribute to compare, e.g. `|f| f.experience_level.clone()`
+///
+/// # Returns: `f64` - Assortativity in [-1, 1], or NaN if the graph has no edges or only one attribute value occurs on them
+pub fn attribute_assortativity<F>(adj_list: &[Vec<usize>], freelancers: &[Freelancer], accessor: F) -> f64
+where
+    F: Fn(&Freelancer) -> String,
+{
+    let values: Vec<String> = freelancers.iter().map(&accessor).collect();
+    let mut same_value_ends = 0usize;
+    let mut ends_per_value: HashMap<&str, usize> = HashMap::new();
+    let mut total_ends = 0usize;
+
+    // The adjacency list holds every edge from both ends, so e_ij is symmetric
+    for (node, neighbors) in adj_list.iter().enumerate() {
+        for &neighbor in neighbors {
+            total_ends += 1;
+            *ends_per_value.entry(values[node].as_str()).or_insert(0) += 1;
+            if values[node] == values[neighbor] {
+                same_value_ends += 1;
+            }
+        }
+    }
+    if total_ends == 0 {
+        return f64::NAN;
+    }
+
+    let total = total_ends as f64;
+    let trace = same_value_ends as f64 / total;
+    let expected: f64 = ends_per_value.values().map(|&count| (count as f64 / total).powi(2)).sum();
+    if (1.0 - expected).abs() < 1e-12 {
+        return f64::NAN;
+    }
+    (trace - expected) / (1.0 - expected)
+}
+
+/// Evaluates how well the similarity threshold recovers known collaboration edges.
+///
+/// Every pair whose `shared_attributes` score is above a threshold (the same strict
+/// comparison the graph builder uses) counts as a predicted edge. Ground-truth pairs
+/// are unordered, so `(a, b)` and `(b, a)` are the same edge.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs, `ground_truth_edges` - Known collaborations,
+/// `thresholds` - Similarity thresholds to evaluate
+///
+/// # Returns: `Vec<(f32, f64, f64)>` - `(threshold, precision, recall)` per threshold. Precision is 1.0 when
+/// no edge is predicted; recall is NaN when there are no ground-truth edges
+pub fn edge_prediction_pr(freelancers: &[Freelancer], ground_truth_edges: &[(usize, usize)], thresholds: &[f32]) -> Vec<(f32, f64, f64)> {
+    let truth: HashSet<(usize, usize)> = ground_truth_edges.iter()
+        .filter(|&&(a, b)| a != b)
+        .map(|&(a, b)| (a.min(b), a.max(b)))
+        .collect();
+
+    let mut scored_pairs = Vec::new();
+    for i in 0..freelancers.len() {
+        for j in (i + 1)..freelancers.len() {
+            scored_pairs.push((shared_attributes(&freelancers[i], &freelancers[j]), truth.contains(&(i, j))));
+        }
+    }
+
+    thresholds.iter()
+        .map(|&threshold| {
+            let mut true_positives = 0usize;
+            let mut predicted = 0usize;
+            for &(score, is_true) in &scored_pairs {
+                if score > threshold {
+                    predicted += 1;
+                    if is_true {
+                        true_positives += 1;
+                    }
+                }
+            }
+            let precision = if predicted == 0 { 1.0 } else { true_positives as f64 / predicted as f64 };
+            let recall = if truth.is_empty() { f64::NAN } else { true_positives as f64 / truth.len() as f64 };
+            (threshold, precision, recall)
+        })
+        .collect()
+}
+
+/// Similarity between job categories, used in place of exact category equality.
+///
+/// Identical categories always score 1.0. Other pairs score 0.0 unless a partial
+/// similarity was registered with `with_pair`, so the default (identity) matrix
+/// reproduces plain equality.
+#[derive(Debug, Clone, Default)]
+pub struct CategorySimilarity {
+    pairs: HashMap<(String, String), f32>,
+}
+
+impl CategorySimilarity {
+    /// Creates the identity matrix: a category is only similar to itself.
+    pub fn identity() -> CategorySimilarity {
+        CategorySimilarity::default()
+    }
+
+    /// Registers a symmetric partial similarity between two categories.
+    ///
+    /// # Arguments: `a`, `b` - Category names, `similarity` - Score, clamped to `[0.0, 1.0]`
+    ///
+    /// # Returns: `CategorySimilarity` - The matrix with the pair added
+    pub fn with_pair(mut self, a: &str, b: &str, similarity: f32) -> CategorySimilarity {
+        self.pairs.insert(category_key(a, b), similarity.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Looks up the similarity between two categories.
+    ///
+    /// # Returns: `f32` - 1.0 for identical categories, the registered score for a known pair, otherwise 0.0
+    pub fn similarity(&self, a: &str, b: &str) -> f32 {
+        if a == b {
+            return 1.0;
+        }
+        self.pairs.get(&category_key(a, b)).copied().unwrap_or(0.0)
+    }
+}
+
+/// Orders a category pair so lookups do not depend on argument order.
+fn category_key(a: &str, b: &str) -> (String, String) {
+    if a <= b {
+        (a.to_string(), b.to_string())
+    } else {
+        (b.to_string(), a.to_string())
+    }
+}
+
+/// Weight each attribute contributes to the shared-attribute similarity.
+///
+/// # Fields
+/// `job_category` - Weight for a matching job category
+/// `platform` - Weight for a matching platform
+/// `client_region` - Weight for a matching client region
+/// `experience_level` - Weight for a matching experience level
+///
+/// The default is 0.3 / 0.25 / 0.25 / 0.2, the weights `shared_attributes` uses.
+/// `SimilarityWeights::new` validates custom weights and scales them to sum to 1.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimilarityWeights {
+    pub job_category: f32,
+    pub platform: f32,
+    pub client_region: f32,
+    pub experience_level: f32,
+}
+
+impl Default for SimilarityWeights {
+    fn default() -> Self {
+        SimilarityWeights {
+            job_category: 0.3,
+            platform: 0.25,
+            client_region: 0.25,
+            experience_level: 0.2,
+        }
+    }
+}
+
+impl SimilarityWeights {
+    /// Creates weights scaled to sum to 1.0, so every score stays in [0, 1].
+    ///
+    /// # Arguments: `job_category`, `platform`, `client_region`, `experience_level` - Relative importance of each attribute
+    ///
+    /// # Returns: `Result<SimilarityWeights, Box<dyn Error>>` - Normalized weights, or an error if any weight
+    /// is negative or not finite, or all are zero
+    pub fn new(job_category: f32, platform: f32, client_region: f32, experience_level: f32) -> Result<SimilarityWeights, Box<dyn Error>> {
+        let weights = [job_category, platform, client_region, experience_level];
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err(format!("similarity weights must be finite and non-negative, got {:?}", weights).into());
+        }
+        let total: f32 = weights.iter().sum();
+        if total <= 0.0 {
+            return Err("at least one similarity weight must be positive".into());
+        }
+        Ok(SimilarityWeights {
+            job_category: job_category / total,
+            platform: platform / total,
+            client_region: client_region / total,
+            experience_level: experience_level / total,
+        })
+    }
+}
+
+/// Calculates similarity score between two freelancers based on shared attributes.
+/// 
+/// # Arguments: `a` - First freelancer to compare, `b` - Second freelancer to compare
+/// 
+/// # Returns: `f32` - Similarity score between 0.0 and 1.
+fn shared_attributes(a: &Freelancer, b: &Freelancer) -> f32 {
+    score_attributes(a, b, &SimilarityWeights::default(), &CategorySimilarity::identity())
+}
+
+/// Calculates the shared-attribute similarity with partial credit for related job categories.
+///
+/// The job category contributes its 0.3 weight scaled by `categories.similarity`;
+/// the other attributes still require exact equality.
+///
+/// # Arguments: `a` - First freelancer to compare, `b` - Second freelancer to compare, `categories` - Category similarity matrix
+///
+/// # Returns: `f32` - Similarity score between 0.0 and 1.
+pub fn shared_attributes_with_categories(a: &Freelancer, b: &Freelancer, categories: &CategorySimilarity) -> f32 {
+    score_attributes(a, b, &SimilarityWeights::default(), categories)
+}
+
+/// Calculates the shared-attribute similarity with custom attribute weights.
+///
+/// Build `weights` with `SimilarityWeights::new` to keep the score in [0, 1].
+///
+/// # Arguments: `a` - First freelancer to compare, `b` - Second freelancer to compare, `weights` - Weight of each attribute
+///
+/// # Returns: `f32` - Sum of the weights of the matching attributes
+pub fn shared_attributes_with_weights(a: &Freelancer, b: &Freelancer, weights: &SimilarityWeights) -> f32 {
+    score_attributes(a, b, weights, &CategorySimilarity::identity())
+}
+
+/// Sums the weights of matching attributes, scaling the category weight by its similarity.
+fn score_attributes(a: &Freelancer, b: &Freelancer, weights: &SimilarityWeights, categories: &CategorySimilarity) -> f32 {
+    let mut count = weights.job_category * categories.similarity(&a.job_category, &b.job_category);
+    if a.platform == b.platform { count += weights.platform; }
+    if a.client_region == b.client_region { count += weights.client_region; }
+    if a.experience_level == b.experience_level { count += weights.experience_level; }
+    count
+}
+
+/// Returns the similarity threshold at which two freelancers stop being connected.
+///
+/// The graph builders add an edge when the score is strictly above the threshold, so
+/// the pair is connected for every threshold below the returned value.
+///
+/// # Arguments: `a` - First freelancer, `b` - Second freelancer, `weights` - Attribute weights
+///
+/// # Returns: `f32` - The pair's `shared_attributes_with_weights` score
+pub fn connection_threshold(a: &Freelancer, b: &Freelancer, weights: &SimilarityWeights) -> f32 {
+    shared_attributes_with_weights(a, b, weights)
+}
+
+/// Lists every other freelancer by the threshold at which they connect to `target`.
+///
+/// # Arguments: `target` - Index of the freelancer of interest, `freelancers` - Slice of Freelancer structs,
+/// `weights` - Attribute weights
+///
+/// # Returns: `Vec<(usize, f32)>` - Other freelancers' indices with their connection threshold, highest first
+/// (ties keep index order)
+pub fn connection_thresholds_for(target: usize, freelancers: &[Freelancer], weights: &SimilarityWeights) -> Vec<(usize, f32)> {
+    let mut thresholds: Vec<(usize, f32)> = freelancers.iter()
+        .enumerate()
+        .filter(|&(i, _)| i != target)
+        .map(|(i, other)| (i, connection_threshold(&freelancers[target], other, weights)))
+        .collect();
+    thresholds.sort_by(|a, b| b.1.total_cmp(&a.1));
+    thresholds
+}
+
+/// Scaling applied to hourly rate and earnings before comparing them.
+///
+/// Differences are divided by the span of each feature. `raw()` leaves values in
+/// dollars; `min_max` divides by the dataset's range so both features live in [0, 1].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumericScaling {
+    pub rate_span: f32,
+    pub earnings_span: f32,
+}
+
+impl NumericScaling {
+    /// Compares raw dollar amounts.
+    pub fn raw() -> NumericScaling {
+        NumericScaling { rate_span: 1.0, earnings_span: 1.0 }
+    }
+
+    /// Scales each feature by its min-max range in `freelancers`; constant features keep a span of 1.
+    pub fn min_max(freelancers: &[Freelancer]) -> NumericScaling {
+        let span = |values: Vec<f32>| {
+            let (low, high) = values.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+            if high > low { high - low } else { 1.0 }
+        };
+        NumericScaling {
+            rate_span: span(freelancers.iter().map(|f| f.hourly_rate).collect()),
+            earnings_span: span(freelancers.iter().map(|f| f.earnings_usd).collect()),
+        }
+    }
+}
+
+/// Similarity of two freelancers' hourly rate and earnings.
+///
+/// `1 - (|Δrate| / rate_span + |Δearnings| / earnings_span) / 2`, clamped to [0, 1].
+///
+/// # Arguments: `a` - First freelancer, `b` - Second freelancer, `scaling` - Feature spans
+///
+/// # Returns: `f32` - 1.0 for identical values, falling to 0.0 as they diverge
+pub fn numeric_similarity(a: &Freelancer, b: &Freelancer, scaling: &NumericScaling) -> f32 {
+    let rate = (a.hourly_rate - b.hourly_rate).abs() / scaling.rate_span;
+    let earnings = (a.earnings_usd - b.earnings_usd).abs() / scaling.earnings_span;
+    (1.0 - (rate + earnings) / 2.0).clamp(0.0, 1.0)
+}
+
+/// Blends the categorical and numeric similarity.
+///
+/// `(1 - numeric_weight) · shared_attributes + numeric_weight · numeric_similarity`, so
+/// a weight of 0.0 reproduces `shared_attributes` exactly.
+///
+/// This is a separate entry point rather than a numeric weight on `shared_attributes`
+/// because the bucketed graph builders (see `build_weighted_graph_with_threshold`) score one
+/// representative per bucket of identical categorical attributes. That shortcut only holds
+/// while `shared_attributes` ignores the numeric fields, which differ within a bucket.
+///
+/// # Arguments: `a` - First freelancer, `b` - Second freelancer, `numeric_weight` - Share given to the numeric part,
+/// clamped to [0, 1], `scaling` - Feature spans
+///
+/// # Returns: `f32` - Similarity score between 0.0 and 1.0
+pub fn blended_similarity(a: &Freelancer, b: &Freelancer, numeric_weight: f32, scaling: &NumericScaling) -> f32 {
+    let w = numeric_weight.clamp(0.0, 1.0);
+    let categorical = shared_attributes(a, b);
+    if w == 0.0 {
+        return categorical;
+    }
+    (1.0 - w) * categorical + w * numeric_similarity(a, b, scaling)
+}
+
+/// Builds the collaboration graph from `blended_similarity`.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs, `threshold` - Minimum similarity (exclusive),
+/// `numeric_weight` - Share of the numeric similarity, `scaling` - Feature spans
+///
+/// # Returns: `Vec<Vec<usize>>` - Adjacency list of the graph
+pub fn build_blended_graph(freelancers: &[Freelancer], threshold: f32, numeric_weight: f32, scaling: &NumericScaling) -> Vec<Vec<usize>> {
+    let n = freelancers.len();
+    let mut adj_list = vec![Vec::new(); n];
+    for i in 0..n {
+        for j in (i + 1)..n {
+            if blended_similarity(&freelancers[i], &freelancers[j], numeric_weight, scaling) > threshold {
+                adj_list[i].push(j);
+                adj_list[j].push(i);
+            }
+        }
+    }
+    adj_list
+}
+
+/// Computes the normalized mutual information between two labelings of the same nodes.
+///
+/// Uses `NMI = 2·I(A; B) / (H(A) + H(B))`. Two labelings that each put every node in
+/// a single group are treated as identical.
+///
+/// # Arguments: `a` - First label per node, `b` - Second label per node
+///
+/// # Returns: `f64` - Agreement in [0, 1], where 1.0 means the partitions are identical up to renaming
+pub fn normalized_mutual_information(a: &[usize], b: &[usize]) -> f64 {
+    let n = a.len().min(b.len());
+    if n == 0 {
+        return 1.0;
+    }
+    let mut joint: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut counts_a: HashMap<usize, usize> = HashMap::new();
+    let mut counts_b: HashMap<usize, usize> = HashMap::new();
+    for i in 0..n {
+        *joint.entry((a[i], b[i])).or_insert(0) += 1;
+        *counts_a.entry(a[i]).or_insert(0) += 1;
+        *counts_b.entry(b[i]).or_insert(0) += 1;
+    }
+
+    let total = n as f64;
+    let entropy = |counts: &HashMap<usize, usize>| -> f64 {
+        counts.values().map(|&c| { let p = c as f64 / total; -p * p.ln() }).sum()
+    };
+    let (h_a, h_b) = (entropy(&counts_a), entropy(&counts_b));
+    if h_a + h_b == 0.0 {
+        return 1.0;
+    }
+
+    let mutual: f64 = joint.iter()
+        .map(|(&(la, lb), &c)| {
+            let p = c as f64 / total;
+            let expected = (counts_a[&la] as f64 / total) * (counts_b[&lb] as f64 / total);
+            p * (p / expected).ln()
+        })
+        .sum();
+    (2.0 * mutual / (h_a + h_b)).clamp(0.0, 1.0)
+}
+
+/// Measures how much min-max normalization of the numeric features changes the clusters.
+///
+/// Builds the blended graph twice, once on raw dollar amounts and once on min-max
+/// normalized ones, and compares their connected components. Raw differences in
+/// dollars dwarf the [0, 1] scale of the numeric similarity, so the two usually differ
+/// whenever `numeric_weight` is above zero.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs, `threshold` - Similarity threshold,
+/// `numeric_weight` - Share of the numeric similarity
+///
+/// # Returns: `f64` - NMI between the two clusterings; a low value means scaling matters
+pub fn compare_clusterings_raw_vs_normalized(freelancers: &[Freelancer], threshold: f32, numeric_weight: f32) -> f64 {
+    let n = freelancers.len();
+    let raw = build_blended_graph(freelancers, threshold, numeric_weight, &NumericScaling::raw());
+    let normalized = build_blended_graph(freelancers, threshold, numeric_weight, &NumericScaling::min_max(freelancers));
+    let raw_labels = cluster_labels(&find_connected_components(&raw), n);
+    let normalized_labels = cluster_labels(&find_connected_components(&normalized), n);
+    normalized_mutual_information(&raw_labels, &normalized_labels)
+}
+
+/// Linkage criterion used to measure the dissimilarity between two clusters.
+///
+/// `Single` uses the closest pair of members, `Complete` the farthest pair and
+/// `Average` the mean over all member pairs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Linkage {
+    Single,
+    Complete,
+    Average,
+}
+
+/// A single merge recorded by agglomerative clustering.
+///
+/// # Fields
+/// `left` - Id of the first merged cluster (the smaller id)
+/// `right` - Id of the second merged cluster
+/// `distance` - Dissimilarity at which the two clusters were merged
+/// `size` - Number of freelancers in the merged cluster
+///
+/// Ids `0..n` refer to individual freelancers; the cluster created by step `k` gets id `n + k`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeStep {
+    pub left: usize,
+    pub right: usize,
+    pub distance: f32,
+    pub size: usize,
+}
+
+/// Builds a dendrogram of freelancers using agglomerative (bottom-up) clustering.
+///
+/// The dissimilarity between two freelancers is `1 - shared_attributes`. Cluster
+/// distances are updated with the Lance-Williams formula for the chosen linkage,
+/// so the run takes O(n³) time and O(n²) memory.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs to cluster, `method` - Linkage criterion
+///
+/// # Returns: `Vec<MergeStep>` - The `n - 1` merges in the order they happened
+pub fn agglomerative_linkage(freelancers: &[Freelancer], method: Linkage) -> Vec<MergeStep> {
+    let n = freelancers.len();
+    let mut dist = vec![vec![0.0f32; n]; n];
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let d = 1.0 - shared_attributes(&freelancers[i], &freelancers[j]);
+            dist[i][j] = d;
+            dist[j][i] = d;
+        }
+    }
+
+    // Slot i holds the current cluster id and size, or None once merged away
+    let mut slots: Vec<Option<(usize, usize)>> = (0..n).map(|i| Some((i, 1))).collect();
+    let mut steps = Vec::with_capacity(n.saturating_sub(1));
+
+    for step in 0..n.saturating_sub(1) {
+        // Find the closest pair of active clusters
+        let mut best: Option<(usize, usize, f32)> = None;
+        for i in 0..n {
+            if slots[i].is_none() { continue; }
+            for j in (i + 1)..n {
+                if slots[j].is_none() { continue; }
+                let closer = match best {
+                    Some((_, _, d)) => dist[i][j] < d,
+                    None => true,
+                };
+                if closer {
+                    best = Some((i, j, dist[i][j]));
+                }
+            }
+        }
+        let (i, j, distance) = match best {
+            Some(pair) => pair,
+            None => break,
+        };
+        let (id_i, size_i) = slots[i].unwrap();
+        let (id_j, size_j) = slots[j].unwrap();
+
+        // Merge j into slot i and update distances to every other active cluster
+        for k in 0..n {
+            if k == i || k == j || slots[k].is_none() { continue; }
+            let updated = match method {
+                Linkage::Single => dist[i][k].min(dist[j][k]),
+                Linkage::Complete => dist[i][k].max(dist[j][k]),
+                Linkage::Average => {
+                    (size_i as f32 * dist[i][k] + size_j as f32 * dist[j][k]) / (size_i + size_j) as f32
+                }
+            };
+            dist[i][k] = updated;
+            dist[k][i] = updated;
+        }
+
+        slots[i] = Some((n + step, size_i + size_j));
+        slots[j] = None;
+        steps.push(MergeStep {
+            left: id_i.min(id_j),
+            right: id_i.max(id_j),
+            distance,
+            size: size_i + size_j,
+        });
+    }
+    steps
+}
+
+/// Frequencies of each categorical value, used to weight matches by rarity.
+///
+/// A value held by `count` of `n` freelancers gets the factor `ln(n / count) / ln(n)`,
+/// so values shared by everyone count for nothing and rare values approach 1.0.
+pub struct IdfTable {
+    n: usize,
+    job_category: HashMap<String, usize>,
+    platform: HashMap<String, usize>,
+    client_region: HashMap<String, usize>,
+    experience_level: HashMap<String, usize>,
+}
+
+impl IdfTable {
+    /// Counts how often each categorical value occurs in the dataset.
+    ///
+    /// # Arguments: `freelancers` - Slice of Freelancer structs to count
+    ///
+    /// # Returns: `IdfTable` - Value frequencies for every categorical attribute
+    pub fn from_freelancers(freelancers: &[Freelancer]) -> IdfTable {
+        let mut table = IdfTable {
+            n: freelancers.len(),
+            job_category: HashMap::new(),
+            platform: HashMap::new(),
+            client_region: HashMap::new(),
+            experience_level: HashMap::new(),
+        };
+        for f in freelancers {
+            *table.job_category.entry(f.job_category.clone()).or_insert(0) += 1;
+            *table.platform.entry(f.platform.clone()).or_insert(0) += 1;
+            *table.client_region.entry(f.client_region.clone()).or_insert(0) += 1;
+            *table.experience_level.entry(f.experience_level.clone()).or_insert(0) += 1;
+        }
+        table
+    }
+
+    /// Returns the rarity factor of a value given its frequency map.
+    fn factor(&self, counts: &HashMap<String, usize>, value: &str) -> f32 {
+        let count = counts.get(value).copied().unwrap_or(0);
+        if self.n < 2 || count == 0 {
+            return 0.0;
+        }
+        ((self.n as f32 / count as f32).ln() / (self.n as f32).ln()).max(0.0)
+    }
+}
+
+/// Calculates a similarity score where each shared attribute is scaled by its rarity.
+///
+/// Each attribute weight is multiplied by the IDF factor of the shared value, so with
+/// normalized weights (see `SimilarityWeights::new`) the score stays between 0.0 and 1.0.
+///
+/// # Arguments: `a` - First freelancer, `b` - Second freelancer, `idf` - Value frequencies of the dataset,
+/// `weights` - Attribute weights, e.g. `SimilarityWeights::default()`
+///
+/// # Returns: `f32` - Rarity-weighted similarity score
+pub fn idf_weighted_similarity(a: &Freelancer, b: &Freelancer, idf: &IdfTable, weights: &SimilarityWeights) -> f32 {
+    let mut score = 0.0;
+    if a.job_category == b.job_category { score += weights.job_category * idf.factor(&idf.job_category, &a.job_category); }
+    if a.platform == b.platform { score += weights.platform * idf.factor(&idf.platform, &a.platform); }
+    if a.client_region == b.client_region { score += weights.client_region * idf.factor(&idf.client_region, &a.client_region); }
+    if a.experience_level == b.experience_level { score += weights.experience_level * idf.factor(&idf.experience_level, &a.experience_level); }
+    score
+}
+
+/// Builds a weighted collaboration graph using rarity-weighted similarity with the default weights.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs, `threshold` - Minimum score (exclusive) for an edge
+///
+/// # Returns: `Vec<Vec<(usize, f32)>>` - Weighted adjacency list of (neighbor, similarity) pairs
+pub fn build_idf_weighted_graph(freelancers: &[Freelancer], threshold: f32) -> Vec<Vec<(usize, f32)>> {
+    let idf = IdfTable::from_freelancers(freelancers);
+    let weights = SimilarityWeights::default();
+    let n = freelancers.len();
+    let mut adj_list = vec![Vec::new(); n];
+
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let score = idf_weighted_similarity(&freelancers[i], &freelancers[j], &idf, &weights);
+            if score > threshold {
+                adj_list[i].push((j, score));
+                adj_list[j].push((i, score));
+            }
+        }
+    }
+    adj_list
+}
+
+/// Distance metric used by `kmeans_cluster`.
+///
+/// `Euclidean` pairs with mean centroids (classic k-means). `Manhattan` pairs with
+/// coordinate-wise median centroids (k-medians), since the median is what minimizes
+/// the summed absolute deviation along each coordinate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distance {
+    Euclidean,
+    Manhattan,
+}
+
+impl Distance {
+    /// Measures the distance between two points of equal dimension.
+    pub fn between(&self, a: &[f64], b: &[f64]) -> f64 {
+        let pairs = a.iter().zip(b.iter());
+        match self {
+            Distance::Euclidean => pairs.map(|(x, y)| (x - y) * (x - y)).sum::<f64>().sqrt(),
+            Distance::Manhattan => pairs.map(|(x, y)| (x - y).abs()).sum(),
+        }
+    }
+}
+
+/// Maximum number of assignment/update rounds run by `kmeans_cluster`.
+const KMEANS_MAX_ITERATIONS: usize = 100;
 
-            while let Some(current) = queue.pop_front() {
-                cluster.push(current);
-                for &neighbor in &adj_list[current] {
-                    if !visited[neighbor] {
-                        visited[neighbor] = true;
-                        queue.push_back(neighbor);
-                    }
-                }
+/// Partitions freelancers into `k` clusters by hourly rate, earnings and job success rate.
+///
+/// Each feature is standardized to zero mean and unit variance first, so no single
+/// one dominates the distance. Initial centroids are chosen deterministically:
+/// the first freelancer, then repeatedly the freelancer farthest from every centroid
+/// chosen so far. Centroids are updated as means for `Euclidean` and as
+/// coordinate-wise medians for `Manhattan`; a centroid that loses all its members
+/// keeps its previous position.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs to cluster, `k` - Number of clusters, `distance` - Distance metric
+///
+/// # Returns: `Vec<Vec<usize>>` - Non-empty clusters of freelancer indices
+pub fn kmeans_cluster(freelancers: &[Freelancer], k: usize, distance: Distance) -> Vec<Vec<usize>> {
+    let points = standardized_features(freelancers);
+    let k = k.min(points.len());
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let mut centroids = vec![points[0].clone()];
+    while centroids.len() < k {
+        let farthest = (0..points.len())
+            .max_by(|&a, &b| {
+                let nearest = |i: usize| {
+                    centroids.iter().map(|c| distance.between(&points[i], c)).fold(f64::INFINITY, f64::min)
+                };
+                nearest(a).total_cmp(&nearest(b))
+            })
+            .unwrap_or(0);
+        centroids.push(points[farthest].clone());
+    }
+
+    let mut assignments = assign_to_nearest(&points, &centroids, distance);
+    for _ in 0..KMEANS_MAX_ITERATIONS {
+        for (c, centroid) in centroids.iter_mut().enumerate() {
+            let members: Vec<&Vec<f64>> = points.iter()
+                .zip(assignments.iter())
+                .filter(|(_, &assigned)| assigned == c)
+                .map(|(point, _)| point)
+                .collect();
+            if let Some(updated) = update_centroid(&members, distance) {
+                *centroid = updated;
             }
-            clusters.push(cluster);
         }
+
+        let next = assign_to_nearest(&points, &centroids, distance);
+        if next == assignments {
+            break;
+        }
+        assignments = next;
+    }
+
+    let mut clusters = vec![Vec::new(); k];
+    for (i, &c) in assignments.iter().enumerate() {
+        clusters[c].push(i);
     }
+    clusters.retain(|cluster| !cluster.is_empty());
     clusters
 }
 
-/// Builds a collaboration graph based on shared attributes between freelancers.
-/// 
-/// # Arguments: `freelancers` - Slice of Freelancer structs to analyze
-/// 
-/// # Returns: `Vec<Vec<usize>>` - Adjacency list representation of the collaboration graph
-pub fn build_collaboration_graph(freelancers: &[Freelancer]) -> Vec<Vec<usize>> {
-    let n = freelancers.len();
-    let mut adj_list = vec![Vec::new(); n];
+/// Z-scores hourly rate, earnings and job success rate; constant features become 0.
+fn standardized_features(freelancers: &[Freelancer]) -> Vec<Vec<f64>> {
+    let raw: Vec<[f64; 3]> = freelancers.iter()
+        .map(|f| [f.hourly_rate as f64, f.earnings_usd as f64, f.job_success_rate as f64])
+        .collect();
+    let n = raw.len() as f64;
 
-    for i in 0..n {
-        for j in (i + 1)..n {
-            if shared_attributes(&freelancers[i], &freelancers[j]) > 0.7 {
-                adj_list[i].push(j);
-                adj_list[j].push(i);
-            }
-        }
+    let mut stats = [(0.0, 0.0); 3];
+    for (d, stat) in stats.iter_mut().enumerate() {
+        let mean = raw.iter().map(|row| row[d]).sum::<f64>() / n;
+        let variance = raw.iter().map(|row| (row[d] - mean).powi(2)).sum::<f64>() / n;
+        *stat = (mean, variance.sqrt());
     }
-    adj_list
+
+    raw.iter()
+        .map(|row| {
+            row.iter()
+                .zip(stats.iter())
+                .map(|(&value, &(mean, std))| if std > 0.0 { (value - mean) / std } else { 0.0 })
+                .collect()
+        })
+        .collect()
 }
 
-/// Calculates similarity score between two freelancers based on shared attributes.
-/// 
-/// # Arguments: `a` - First freelancer to compare, `b` - Second freelancer to compare
-/// 
-/// # Returns: `f32` - Similarity score between 0.0 and 1.
-fn shared_attributes(a: &Freelancer, b: &Freelancer) -> f32 {
-    let mut count = 0.0;
-    if a.job_category == b.job_category { count += 0.3; }
-    if a.platform == b.platform { count += 0.25; }
-    if a.client_region == b.client_region { count += 0.25; }
-    if a.experience_level == b.experience_level { count += 0.2; }
-    count
+/// Assigns every point to its nearest centroid; ties go to the lower centroid index.
+fn assign_to_nearest(points: &[Vec<f64>], centroids: &[Vec<f64>], distance: Distance) -> Vec<usize> {
+    points.iter()
+        .map(|point| {
+            let mut best = 0;
+            let mut best_distance = f64::INFINITY;
+            for (c, centroid) in centroids.iter().enumerate() {
+                let d = distance.between(point, centroid);
+                if d < best_distance {
+                    best = c;
+                    best_distance = d;
+                }
+            }
+            best
+        })
+        .collect()
 }
 
+/// Recomputes a centroid from its members: the mean for `Euclidean`, the
+/// coordinate-wise median for `Manhattan`. Returns `None` for an empty cluster.
+fn update_centroid(members: &[&Vec<f64>], distance: Distance) -> Option<Vec<f64>> {
+    let dims = members.first()?.len();
+    let centroid = (0..dims)
+        .map(|d| {
+            let mut values: Vec<f64> = members.iter().map(|point| point[d]).collect();
+            match distance {
+                Distance::Euclidean => values.iter().sum::<f64>() / values.len() as f64,
+                Distance::Manhattan => {
+                    values.sort_by(|a, b| a.total_cmp(b));
+                    let mid = values.len() / 2;
+                    if values.len() % 2 == 1 {
+                        values[mid]
+                    } else {
+                        (values[mid - 1] + values[mid]) / 2.0
+                    }
+                }
+            }
+        })
+        .collect();
+    Some(centroid)
+}
 
 /// Creates test data for unit testing
+#[cfg(test)]
 fn create_test_freelancers() -> Vec<Freelancer> {
     vec![
-        Freelancer {
-            id: 1,
-            job_category: "Web Development".to_string(),
-            platform: "Upwork".to_string(),
-            client_region: "USA".to_string(),
-            experience_level: "Expert".to_string(),
-            earnings_usd: 0.0,
-            hourly_rate: 0.0,
-        },
-        Freelancer {
-            id: 2,
-            job_category: "Web Development".to_string(),
-            platform: "Upwork".to_string(),
-            client_region: "USA".to_string(),
-            experience_level: "Expert".to_string(),
-            earnings_usd: 0.0,
-            hourly_rate: 0.0,
-        },
-        Freelancer {
-            id: 3,
-            job_category: "Design".to_string(),
-            platform: "Fiverr".to_string(),
-            client_region: "Europe".to_string(),
-            experience_level: "Beginner".to_string(),
-            earnings_usd: 0.0,
-            hourly_rate: 0.0,
-        },
+        FreelancerBuilder::new().id(1).experience_level("Expert").build(),
+        FreelancerBuilder::new().id(2).experience_level("Expert").build(),
+        FreelancerBuilder::new()
+            .id(3)
+            .job_category("Design")
+            .platform("Fiverr")
+            .client_region("Europe")
+            .experience_level("Beginner")
+            .build(),
     ]
 }
 
@@ -144,6 +1999,7 @@ fn test_shared_attributes() {
         experience_level: "Expert".to_string(),
         earnings_usd: 0.0,
         hourly_rate: 0.0,
+        job_success_rate: 0.0,
     };
     
     let f2 = Freelancer {
@@ -154,9 +2010,696 @@ fn test_shared_attributes() {
         experience_level: "Intermediate".to_string(),
         earnings_usd: 0.0,
         hourly_rate: 0.0,
+        job_success_rate: 0.0,
     };
     
     // Should have 0.55 similarity (0.3 + 0.25)
     assert_eq!(shared_attributes(&f1, &f2), 0.55);
 }
 
+/// Tests that agglomerative linkage merges the most similar freelancers first
+#[test]
+fn test_agglomerative_linkage_merge_order() {
+    let mut freelancers = create_test_freelancers();
+    // Same as the first two except for experience level (dissimilarity 0.2)
+    freelancers.insert(2, Freelancer {
+        id: 4,
+        job_category: "Web Development".to_string(),
+        platform: "Upwork".to_string(),
+        client_region: "USA".to_string(),
+        experience_level: "Beginner".to_string(),
+        earnings_usd: 0.0,
+        hourly_rate: 0.0,
+        job_success_rate: 0.0,
+    });
+
+    let steps = agglomerative_linkage(&freelancers, Linkage::Single);
+    assert_eq!(steps.len(), 3);
+    assert_eq!((steps[0].left, steps[0].right, steps[0].size), (0, 1, 2));
+    assert!(steps[0].distance.abs() < 1e-6);
+    assert_eq!((steps[1].left, steps[1].right, steps[1].size), (2, 4, 3));
+    assert!((steps[1].distance - 0.2).abs() < 1e-6);
+    assert_eq!((steps[2].left, steps[2].right, steps[2].size), (3, 5, 4));
+    assert!((steps[2].distance - 0.8).abs() < 1e-6);
+
+    // Complete linkage joins the outlier at its farthest distance instead
+    let complete = agglomerative_linkage(&freelancers, Linkage::Complete);
+    assert!((complete[2].distance - 1.0).abs() < 1e-6);
+}
+
+/// Tests modularity on two disconnected triangles
+#[test]
+fn test_modularity_two_triangles() {
+    let adj_list = vec![
+        vec![1, 2], vec![0, 2], vec![0, 1],
+        vec![4, 5], vec![3, 5], vec![3, 4],
+    ];
+    let clusters = vec![vec![0, 1, 2], vec![3, 4, 5]];
+    // Each triangle holds half the edges and half the degree: 2 * (0.5 - 0.25)
+    assert!((modularity(&adj_list, &clusters) - 0.5).abs() < 1e-9);
+}
+
+/// Tests that the best threshold comes from the candidate set with a finite modularity
+#[test]
+fn test_best_threshold_by_modularity() {
+    let freelancers = create_test_freelancers();
+    let candidates = [0.3, 0.5, 0.7, 0.9];
+    let (threshold, q) = best_threshold_by_modularity(&freelancers, &candidates);
+    assert!(candidates.contains(&threshold));
+    assert!(q.is_finite());
+}
+
+/// Tests that matching on a rare category yields a heavier edge than a common one
+#[test]
+fn test_idf_weighted_graph_favors_rare_categories() {
+    let categories = ["Web Development", "Web Development", "Web Development", "Web Development", "Writing", "Writing"];
+    let freelancers: Vec<Freelancer> = categories.iter()
+        .enumerate()
+        .map(|(i, category)| Freelancer {
+            id: i as u32,
+            job_category: category.to_string(),
+            platform: "Upwork".to_string(),
+            client_region: "USA".to_string(),
+            experience_level: "Expert".to_string(),
+            earnings_usd: 0.0,
+            hourly_rate: 0.0,
+            job_success_rate: 0.0,
+        })
+        .collect();
+
+    let graph = build_idf_weighted_graph(&freelancers, 0.0);
+    let weight = |i: usize, j: usize| graph[i].iter().find(|(n, _)| *n == j).map(|(_, w)| *w);
+
+    // Attributes shared by everyone carry no weight, so only category matches form edges
+    let common = weight(0, 1).unwrap();
+    let rare = weight(4, 5).unwrap();
+    assert!(rare > common);
+    assert!(weight(0, 4).is_none());
+
+    // Only the category weight matters here, so doubling it relative to the rest doubles the score
+    let idf = IdfTable::from_freelancers(&freelancers);
+    let heavy = SimilarityWeights { job_category: 0.6, ..SimilarityWeights::default() };
+    let doubled = idf_weighted_similarity(&freelancers[4], &freelancers[5], &idf, &heavy);
+    assert!((doubled - 2.0 * rare).abs() < 1e-6);
+}
+
+/// Tests clustering coefficients of a triangle, a star and a path
+#[test]
+fn test_clustering_coefficient() {
+    let triangle = vec![vec![1, 2], vec![0, 2], vec![0, 1]];
+    let (average, local) = clustering_coefficient(&triangle);
+    assert_eq!(average, 1.0);
+    assert_eq!(local, vec![1.0, 1.0, 1.0]);
+
+    let star = vec![vec![1, 2, 3], vec![0], vec![0], vec![0]];
+    let (average, local) = clustering_coefficient(&star);
+    assert_eq!(average, 0.0);
+    assert_eq!(local, vec![0.0, 0.0, 0.0, 0.0]);
+
+    // Endpoints of a path have one neighbor; the middle node's neighbors are not linked
+    let path = vec![vec![1], vec![0, 2], vec![1]];
+    assert_eq!(clustering_coefficient(&path).1, vec![0.0, 0.0, 0.0]);
+}
+
+/// Tests that the component iterator yields the first cluster without exploring the rest
+#[test]
+fn test_components_iter_is_lazy() {
+    let adj_list = vec![
+        vec![1],
+        vec![0, 2],
+        vec![1],
+        vec![4],
+        vec![3],
+    ];
+
+    let mut iter = components_iter(&adj_list);
+    let first = iter.next().unwrap();
+    assert_eq!(first, find_connected_components(&adj_list)[0]);
+    // Only the first cluster's nodes have been visited so far
+    assert_eq!(iter.visited.iter().filter(|&&v| v).count(), 3);
+
+    assert_eq!(iter.next(), Some(vec![3, 4]));
+    assert_eq!(iter.next(), None);
+}
+
+/// Tests that the graph size guard triggers above the limit and can be bypassed
+#[test]
+fn test_build_collaboration_graph_checked() {
+    let freelancers = create_test_freelancers();
+
+    let error = build_collaboration_graph_checked(&freelancers, 2, false).unwrap_err().to_string();
+    assert!(error.contains("allow_large"));
+    assert!(error.contains("3 comparisons across 2 attribute buckets"), "{}", error);
+
+    let graph = build_collaboration_graph_checked(&freelancers, 2, true).unwrap();
+    assert_eq!(graph, build_collaboration_graph(&freelancers));
+    assert!(build_collaboration_graph_checked(&freelancers, 3, false).is_ok());
+}
+
+/// Tests the eigengap estimate on two dense groups joined by a single edge
+#[test]
+fn test_estimate_clusters_eigengap() {
+    // Two 4-cliques (0-3 and 4-7) connected by the edge 3-4
+    let mut adj_list = vec![Vec::new(); 8];
+    for group in [0..4, 4..8] {
+        for i in group.clone() {
+            for j in group.clone() {
+                if i != j {
+                    adj_list[i].push(j);
+                }
+            }
+        }
+    }
+    adj_list[3].push(4);
+    adj_list[4].push(3);
+
+    assert_eq!(estimate_clusters_eigengap(&adj_list, 5), 2);
+
+    // Jacobi recovers the known spectrum of a triangle's normalized Laplacian
+    let triangle = vec![vec![1, 2], vec![0, 2], vec![0, 1]];
+    let laplacian = vec![vec![1.0, -0.5, -0.5], vec![-0.5, 1.0, -0.5], vec![-0.5, -0.5, 1.0]];
+    let eigenvalues = symmetric_eigenvalues(laplacian);
+    assert!(eigenvalues[0].abs() < 1e-9);
+    assert!((eigenvalues[1] - 1.5).abs() < 1e-9 && (eigenvalues[2] - 1.5).abs() < 1e-9);
+    assert_eq!(estimate_clusters_eigengap(&triangle, 2), 1);
+}
+
+/// Tests the cross-category edge fraction on a path with one crossing edge out of four
+#[test]
+fn test_cross_category_edge_fraction() {
+    let mut freelancers = create_test_freelancers();
+    freelancers.push(Freelancer { id: 4, job_category: "Design".to_string(), ..Default::default() });
+    freelancers.push(Freelancer { id: 5, job_category: "Design".to_string(), ..Default::default() });
+
+    // Path 0-1-2-3-4: only 1-2 joins Web Development to Design
+    let adj_list = vec![vec![1], vec![0, 2], vec![1, 3], vec![2, 4], vec![3]];
+    assert!((cross_category_edge_fraction(&adj_list, &freelancers) - 0.25).abs() < 1e-12);
+    assert_eq!(cross_category_edge_fraction(&vec![Vec::new(); 5], &freelancers), 0.0);
+}
+
+/// Tests that Euclidean and Manhattan distance can assign points and cluster data differently
+#[test]
+fn test_kmeans_distance_changes_assignment() {
+    // (3, 0) is 3.0 from both metrics' view of the origin, but 2.66 (Euclidean)
+    // versus 3.7 (Manhattan) from (4.5, 2.2)
+    let points = vec![vec![3.0, 0.0]];
+    let centroids = vec![vec![0.0, 0.0], vec![4.5, 2.2]];
+    assert_eq!(assign_to_nearest(&points, &centroids, Distance::Euclidean), [1]);
+    assert_eq!(assign_to_nearest(&points, &centroids, Distance::Manhattan), [0]);
+
+    // The Manhattan centroid is the coordinate-wise median, unaffected by the outlier
+    let members = [vec![0.0, 1.0], vec![1.0, 2.0], vec![100.0, 3.0]];
+    let refs: Vec<&Vec<f64>> = members.iter().collect();
+    assert_eq!(update_centroid(&refs, Distance::Manhattan).unwrap(), [1.0, 2.0]);
+    assert_eq!(update_centroid(&refs, Distance::Euclidean).unwrap(), [101.0 / 3.0, 2.0]);
+
+    // Both metrics separate two well-spaced rate groups
+    let freelancers: Vec<Freelancer> = [10.0, 11.0, 12.0, 90.0, 91.0, 92.0].iter()
+        .map(|&rate| Freelancer { hourly_rate: rate, earnings_usd: rate * 100.0, job_success_rate: 80.0, ..Default::default() })
+        .collect();
+    for distance in [Distance::Euclidean, Distance::Manhattan] {
+        assert_eq!(kmeans_cluster(&freelancers, 2, distance), vec![vec![0, 1, 2], vec![3, 4, 5]]);
+    }
+
+    // Without a clear gap the metrics settle on different final clusterings
+    let freelancers: Vec<Freelancer> = [(80.0, 4000.0), (20.0, 2000.0), (50.0, 7000.0), (10.0, 5000.0), (10.0, 5000.0), (20.0, 4000.0)].iter()
+        .map(|&(rate, earnings)| Freelancer { hourly_rate: rate, earnings_usd: earnings, job_success_rate: 80.0, ..Default::default() })
+        .collect();
+    assert_eq!(kmeans_cluster(&freelancers, 2, Distance::Euclidean), vec![vec![0], vec![1, 2, 3, 4, 5]]);
+    assert_eq!(kmeans_cluster(&freelancers, 2, Distance::Manhattan), vec![vec![0, 2], vec![1, 3, 4, 5]]);
+}
+
+/// Tests that related categories contribute partial weight while the identity matrix does not
+#[test]
+fn test_shared_attributes_with_category_similarity() {
+    let web = Freelancer { job_category: "Web Development".to_string(), ..Default::default() };
+    let mobile = Freelancer { job_category: "Mobile Development".to_string(), ..Default::default() };
+    let writing = Freelancer { job_category: "Writing".to_string(), ..Default::default() };
+    // Empty platform, region and experience are equal, contributing 0.7
+    let base = 0.7;
+
+    let identity = CategorySimilarity::identity();
+    assert!((shared_attributes_with_categories(&web, &mobile, &identity) - base).abs() < 1e-6);
+    assert_eq!(shared_attributes_with_categories(&web, &mobile, &identity), shared_attributes(&web, &mobile));
+
+    let related = CategorySimilarity::identity().with_pair("Web Development", "Mobile Development", 0.5);
+    assert_eq!(related.similarity("Mobile Development", "Web Development"), 0.5);
+    assert!((shared_attributes_with_categories(&web, &mobile, &related) - (base + 0.15)).abs() < 1e-6);
+    assert!((shared_attributes_with_categories(&web, &writing, &related) - base).abs() < 1e-6);
+}
+
+/// Tests modularity gains on a triangle with a pendant node, computed by hand
+#[test]
+fn test_modularity_gain() {
+    // Triangle 0-1-2 plus the edge 2-3, all with unit weight: 2m = 8
+    let edges = [(0, 1), (0, 2), (1, 2), (2, 3)];
+    let mut weighted_adj = vec![Vec::new(); 4];
+    for &(a, b) in &edges {
+        weighted_adj[a].push((b, 1.0));
+        weighted_adj[b].push((a, 1.0));
+    }
+    let partition = [0, 0, 0, 1];
+
+    // Q = (3/4 - (7/8)²) + (0 - (1/8)²) = -1/32
+    assert!((weighted_modularity(&weighted_adj, &partition) + 1.0 / 32.0).abs() < 1e-12);
+    // Joining 3 to the triangle gives Q = 1 - 1 = 0
+    assert!((modularity_gain(&weighted_adj, &partition, 3, 0) - 1.0 / 32.0).abs() < 1e-12);
+    // Moving 0 next to 3 gives Q = (1/4 - (5/8)²) - (3/8)² = -9/32
+    assert!((modularity_gain(&weighted_adj, &partition, 0, 1) + 0.25).abs() < 1e-12);
+    assert_eq!(modularity_gain(&weighted_adj, &partition, 1, 0), 0.0);
+
+    // Unit weights agree with the unweighted modularity
+    let adj_list: Vec<Vec<usize>> = weighted_adj.iter().map(|n| n.iter().map(|&(j, _)| j).collect()).collect();
+    let unweighted = modularity(&adj_list, &[vec![0, 1, 2], vec![3]]);
+    assert!((weighted_modularity(&weighted_adj, &partition) - unweighted).abs() < 1e-12);
+}
+
+/// Tests that fully connected same-level groups are perfectly assortative by experience
+#[test]
+fn test_attribute_assortativity() {
+    let levels = ["Expert", "Expert", "Expert", "Beginner", "Beginner", "Beginner"];
+    let freelancers: Vec<Freelancer> = levels.iter()
+        .map(|level| Freelancer { experience_level: level.to_string(), ..Default::default() })
+        .collect();
+
+    // Two triangles, one per experience level
+    let mut adj_list = vec![vec![1, 2], vec![0, 2], vec![0, 1], vec![4, 5], vec![3, 5], vec![3, 4]];
+    let by_level = |f: &Freelancer| f.experience_level.clone();
+    assert!((attribute_assortativity(&adj_list, &freelancers, by_level) - 1.0).abs() < 1e-12);
+
+    // One cross-level edge out of seven: e_ii sums to 12/14 and each a_i is 1/2, so r = 5/7
+    adj_list[2].push(3);
+    adj_list[3].push(2);
+    assert!((attribute_assortativity(&adj_list, &freelancers, by_level) - 5.0 / 7.0).abs() < 1e-12);
+
+    assert!(attribute_assortativity(&vec![Vec::new(); 6], &freelancers, by_level).is_nan());
+}
+
+/// Tests precision and recall of edge prediction against a tiny ground truth
+#[test]
+fn test_edge_prediction_pr() {
+    // Pair (0, 1) scores 1.0, both pairs with 2 score 0.0
+    let freelancers = create_test_freelancers();
+    let ground_truth = [(1, 0), (1, 2)];
+
+    let curve = edge_prediction_pr(&freelancers, &ground_truth, &[-0.5, 0.5, 1.0]);
+    // Everything predicted: one of three pairs is wrong
+    assert_eq!(curve[0], (-0.5, 2.0 / 3.0, 1.0));
+    // Only (0, 1) predicted: correct, but (1, 2) is missed
+    assert_eq!(curve[1], (0.5, 1.0, 0.5));
+    // Nothing predicted
+    assert_eq!(curve[2], (1.0, 1.0, 0.0));
+}
+
+/// Tests that components have no inter-cluster edges while a community split does
+#[test]
+fn test_intra_vs_inter_edges() {
+    // Two triangles joined by the edge 2-3, plus an isolated node 6
+    let mut adj_list = vec![vec![1, 2], vec![0, 2], vec![0, 1, 3], vec![2, 4, 5], vec![3, 5], vec![3, 4], vec![]];
+
+    let components = find_connected_components(&adj_list);
+    let labels = cluster_labels(&components, adj_list.len());
+    assert_eq!(intra_vs_inter_edges(&adj_list, &labels), (7, 0));
+
+    let communities = cluster_labels(&[vec![0, 1, 2], vec![3, 4, 5], vec![6]], adj_list.len());
+    assert_eq!(intra_vs_inter_edges(&adj_list, &communities), (6, 1));
+
+    adj_list[6].push(0);
+    adj_list[0].push(6);
+    assert_eq!(intra_vs_inter_edges(&adj_list, &communities), (6, 2));
+}
+
+/// Tests that the connection threshold equals the similarity score and ranks other freelancers
+#[test]
+fn test_connection_threshold() {
+    let mut freelancers = create_test_freelancers();
+    // Shares platform and region with freelancer 0
+    freelancers.push(Freelancer {
+        id: 4,
+        job_category: "Design".to_string(),
+        platform: "Upwork".to_string(),
+        client_region: "USA".to_string(),
+        experience_level: "Beginner".to_string(),
+        ..Default::default()
+    });
+    let weights = SimilarityWeights::default();
+
+    let threshold = connection_threshold(&freelancers[0], &freelancers[3], &weights);
+    assert_eq!(threshold, shared_attributes(&freelancers[0], &freelancers[3]));
+    assert!((threshold - 0.5).abs() < 1e-6);
+
+    let ranked = connection_thresholds_for(0, &freelancers, &weights);
+    let order: Vec<usize> = ranked.iter().map(|&(i, _)| i).collect();
+    assert_eq!(order, vec![1, 3, 2]);
+}
+
+/// Tests that raw and normalized numeric features cluster identically when their weight is zero
+#[test]
+fn test_compare_clusterings_raw_vs_normalized() {
+    let mut freelancers = create_test_freelancers();
+    for (f, (rate, earnings)) in freelancers.iter_mut().zip([(20.0, 1000.0), (22.0, 1100.0), (90.0, 9000.0)]) {
+        f.hourly_rate = rate;
+        f.earnings_usd = earnings;
+    }
+    freelancers.push(Freelancer { hourly_rate: 21.0, earnings_usd: 1050.0, ..freelancers[2].clone() });
+
+    assert_eq!(compare_clusterings_raw_vs_normalized(&freelancers, 0.5, 0.0), 1.0);
+    // With normalization 2 and 3 become close enough to connect; raw dollars keep them apart
+    assert!(compare_clusterings_raw_vs_normalized(&freelancers, 0.5, 0.6) < 1.0);
+
+    assert_eq!(normalized_mutual_information(&[0, 0, 1, 1], &[5, 5, 2, 2]), 1.0);
+    assert!(normalized_mutual_information(&[0, 0, 1, 1], &[0, 1, 0, 1]).abs() < 1e-12);
+}
+
+/// Tests that lowering the threshold adds edges and out-of-range thresholds are rejected
+#[test]
+fn test_build_collaboration_graph_with_threshold() {
+    let mut freelancers = create_test_freelancers();
+    freelancers[2].platform = freelancers[0].platform.clone();
+    let edge_count = |graph: &[Vec<usize>]| graph.iter().map(Vec::len).sum::<usize>() / 2;
+
+    let dense = build_collaboration_graph_with_threshold(&freelancers, 0.0).unwrap();
+    let sparse = build_collaboration_graph_with_threshold(&freelancers, 0.8).unwrap();
+    assert!(edge_count(&dense) > edge_count(&sparse));
+    assert_eq!(
+        build_collaboration_graph_with_threshold(&freelancers, DEFAULT_SIMILARITY_THRESHOLD).unwrap(),
+        build_collaboration_graph(&freelancers)
+    );
+    assert_eq!(edge_count(&build_collaboration_graph_with_threshold(&freelancers, 1.0).unwrap()), 0);
+
+    assert!(build_collaboration_graph_with_threshold(&freelancers, 1.5).is_err());
+    assert!(build_collaboration_graph_with_threshold(&freelancers, -0.1).is_err());
+    assert!(build_collaboration_graph_with_threshold(&freelancers, f32::NAN).is_err());
+}
+
+/// Tests that the knee of a bimodal similarity distribution falls in the valley between the modes
+#[test]
+fn test_similarity_knee() {
+    let freelancer = |category: &str, platform: &str, region: &str, experience: &str| Freelancer {
+        job_category: category.to_string(),
+        platform: platform.to_string(),
+        client_region: region.to_string(),
+        experience_level: experience.to_string(),
+        ..Default::default()
+    };
+    // Within each group scores are 0.8 or 1.0; across groups only experience can match (0.0 or 0.2)
+    let freelancers: Vec<Freelancer> = ["Expert", "Expert", "Expert", "Beginner", "Beginner"]
+        .iter()
+        .flat_map(|&experience| [
+            freelancer("Web Development", "Upwork", "USA", experience),
+            freelancer("Design", "Fiverr", "Europe", experience),
+        ])
+        .collect();
+
+    let knee = similarity_knee(&freelancers);
+    assert!(knee > 0.2 && knee < 0.8, "knee {} outside the valley", knee);
+    assert_eq!(similarity_knee(&freelancers[..2]), DEFAULT_SIMILARITY_THRESHOLD);
+}
+
+/// Tests that custom weights are normalized and change the score of a pair sharing only experience level
+#[test]
+fn test_similarity_weights() {
+    let freelancers = create_test_freelancers();
+    let only_experience = Freelancer {
+        experience_level: freelancers[0].experience_level.clone(),
+        ..freelancers[2].clone()
+    };
+    let default_score = shared_attributes(&freelancers[0], &only_experience);
+    assert!((default_score - 0.2).abs() < 1e-6);
+
+    let weights = SimilarityWeights::new(1.0, 1.0, 1.0, 2.0).unwrap();
+    let total = weights.job_category + weights.platform + weights.client_region + weights.experience_level;
+    assert!((total - 1.0).abs() < 1e-6);
+    let weighted_score = shared_attributes_with_weights(&freelancers[0], &only_experience, &weights);
+    assert!((weighted_score - 0.4).abs() < 1e-6);
+    assert_eq!(shared_attributes_with_weights(&freelancers[0], &freelancers[1], &weights), 1.0);
+
+    assert_eq!(SimilarityWeights::new(0.3, 0.25, 0.25, 0.2).unwrap(), SimilarityWeights::default());
+    assert!(SimilarityWeights::new(0.0, 0.0, 0.0, 0.0).is_err());
+    assert!(SimilarityWeights::new(0.5, -0.1, 0.3, 0.3).is_err());
+}
+
+/// Tests that the bucketed graph builder matches the pairwise reference implementation
+#[test]
+fn test_bucketed_graph_matches_naive() {
+    let mut freelancers = super::data_loader::generate_synthetic_freelancers(300, 7);
+    freelancers.extend(create_test_freelancers());
+
+    for threshold in [0.0, 0.3, 0.5, DEFAULT_SIMILARITY_THRESHOLD, 0.8, 1.0] {
+        assert_eq!(build_graph_with_threshold(&freelancers, threshold), build_graph_naive(&freelancers, threshold));
+    }
+    assert!(build_graph_with_threshold(&[], 0.5).is_empty());
+}
+
+/// Tests that union-find components equal the BFS components
+#[test]
+fn test_find_connected_components_union_find() {
+    let sorted = |mut clusters: Vec<Vec<usize>>| {
+        clusters.iter_mut().for_each(|c| c.sort_unstable());
+        clusters.sort();
+        clusters
+    };
+    let adj_list = vec![vec![3], vec![], vec![4, 5], vec![0], vec![2], vec![2], vec![]];
+    assert_eq!(
+        sorted(find_connected_components_union_find(&adj_list)),
+        sorted(find_connected_components(&adj_list))
+    );
+
+    let graph = build_collaboration_graph(&super::data_loader::generate_synthetic_freelancers(200, 3));
+    assert_eq!(
+        sorted(find_connected_components_union_find(&graph)),
+        sorted(find_connected_components(&graph))
+    );
+
+    let mut sets = UnionFind::new(3);
+    assert!(sets.union(0, 2));
+    assert!(!sets.union(2, 0));
+    assert_eq!(sets.components(), vec![vec![0, 2], vec![1]]);
+}
+
+/// Tests articulation points on two triangles joined through a single node, plus a path
+#[test]
+fn test_cluster_critical_nodes() {
+    // Triangles 0-1-2 and 3-4-5 joined by node 6; path 7-8-9; isolated node 10
+    let edges = [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 6), (6, 3), (7, 8), (8, 9)];
+    let mut adj_list = vec![Vec::new(); 11];
+    for &(a, b) in &edges {
+        adj_list[a].push(b);
+        adj_list[b].push(a);
+    }
+    assert_eq!(cluster_critical_nodes(&adj_list), vec![2, 3, 6, 8]);
+
+    // Removing a critical node increases the component count
+    let components = find_connected_components(&adj_list).len();
+    let without_6: Vec<Vec<usize>> = adj_list.iter()
+        .enumerate()
+        .map(|(i, neighbors)| if i == 6 { Vec::new() } else { neighbors.iter().copied().filter(|&j| j != 6).collect() })
+        .collect();
+    assert!(find_connected_components(&without_6).len() > components);
+
+    // A cycle has no critical nodes
+    assert!(cluster_critical_nodes(&[vec![1, 2], vec![0, 2], vec![0, 1]]).is_empty());
+}
+
+/// Tests that weighted edges carry the pair's similarity and match the unweighted graph
+#[test]
+fn test_build_weighted_collaboration_graph() {
+    let mut freelancers = create_test_freelancers();
+    // Shares everything but experience level with freelancer 0
+    freelancers.push(Freelancer { experience_level: "Beginner".to_string(), ..freelancers[0].clone() });
+
+    let weighted = build_weighted_collaboration_graph(&freelancers);
+    assert_eq!(to_unweighted(&weighted), build_collaboration_graph(&freelancers));
+    for (i, neighbors) in weighted.iter().enumerate() {
+        for &(j, weight) in neighbors {
+            assert_eq!(weight, shared_attributes(&freelancers[i], &freelancers[j]));
+        }
+    }
+    assert_eq!(weighted[0], vec![(1, 1.0), (3, shared_attributes(&freelancers[0], &freelancers[3]))]);
+    assert!(weighted[2].is_empty());
+}
+
+/// Tests that matching hourly rates raise the blended similarity of categorically identical freelancers
+#[test]
+fn test_blended_similarity_rewards_matching_rates() {
+    let mut freelancers = create_test_freelancers();
+    freelancers[0].hourly_rate = 30.0;
+    freelancers[1].hourly_rate = 30.0;
+    freelancers[2].hourly_rate = 120.0;
+    let far_apart = Freelancer { hourly_rate: 120.0, ..freelancers[0].clone() };
+    freelancers.push(far_apart);
+    let scaling = NumericScaling::min_max(&freelancers);
+
+    let matching = blended_similarity(&freelancers[0], &freelancers[1], 0.3, &scaling);
+    let distant = blended_similarity(&freelancers[0], &freelancers[3], 0.3, &scaling);
+    assert_eq!(shared_attributes(&freelancers[0], &freelancers[3]), 1.0);
+    assert!(matching > distant);
+    assert!((matching - 1.0).abs() < 1e-6);
+    // Rescaling the dollar amounts leaves min-max similarities unchanged
+    let scaled: Vec<Freelancer> = freelancers.iter()
+        .map(|f| Freelancer { hourly_rate: f.hourly_rate * 10.0, earnings_usd: f.earnings_usd * 10.0, ..f.clone() })
+        .collect();
+    let scaled_distant = blended_similarity(&scaled[0], &scaled[3], 0.3, &NumericScaling::min_max(&scaled));
+    assert!((scaled_distant - distant).abs() < 1e-6);
+}
+
+/// Tests that largest_component picks the biggest cluster, the first on ties, and None for no clusters
+#[test]
+fn test_largest_component() {
+    let clusters = vec![vec![0, 1], vec![2, 3, 4], vec![5]];
+    assert_eq!(largest_component(&clusters), Some(&vec![2, 3, 4]));
+    assert_eq!(largest_component(&[vec![0, 1], vec![2, 3]]), Some(&vec![0, 1]));
+    assert_eq!(largest_component(&[]), None);
+}
+
+/// Tests that filtering with a minimum size of 2 removes singletons and keeps larger clusters intact
+#[test]
+fn test_filter_clusters_by_size() {
+    let clusters = vec![vec![0], vec![3, 1, 2], vec![4], vec![5, 6]];
+    assert_eq!(filter_clusters_by_size(clusters.clone(), 2), vec![vec![3, 1, 2], vec![5, 6]]);
+    assert_eq!(filter_clusters_by_size(clusters.clone(), 0), clusters);
+    assert!(filter_clusters_by_size(clusters, 4).is_empty());
+}
+
+/// Tests degree centrality on a star graph and a single node
+#[test]
+fn test_degree_centrality() {
+    // Node 0 is the center of a star with four leaves
+    let star = vec![vec![1, 2, 3, 4], vec![0], vec![0], vec![0], vec![0]];
+    assert_eq!(degree_centrality(&star), vec![1.0, 0.25, 0.25, 0.25, 0.25]);
+    assert_eq!(degree_centrality(&[vec![]]), vec![0.0]);
+    assert!(degree_centrality(&[]).is_empty());
+}
+
+/// Tests that DFS components match the BFS components and follow DFS preorder
+#[test]
+fn test_find_connected_components_dfs() {
+    let sorted = |mut clusters: Vec<Vec<usize>>| {
+        clusters.iter_mut().for_each(|c| c.sort_unstable());
+        clusters.sort();
+        clusters
+    };
+    let graph = build_collaboration_graph(&super::data_loader::generate_synthetic_freelancers(200, 5));
+    assert_eq!(sorted(find_connected_components_dfs(&graph)), sorted(find_connected_components(&graph)));
+
+    // 0 - 1 - 3 and 0 - 2: DFS goes deep through 1 before visiting 2
+    let adj_list = vec![vec![1, 2], vec![0, 3], vec![0], vec![1], vec![]];
+    assert_eq!(find_connected_components_dfs(&adj_list), vec![vec![0, 1, 3, 2], vec![4]]);
+    assert_eq!(find_connected_components(&adj_list), vec![vec![0, 1, 2, 3], vec![4]]);
+}
+
+/// Tests that mentorship edges point only from the expert to the beginner on the same platform
+#[test]
+fn test_build_directed_graph() {
+    let mut freelancers = create_test_freelancers();
+    freelancers.truncate(3);
+    for (f, (level, platform)) in freelancers.iter_mut().zip([("Expert", "Upwork"), ("Beginner", "Upwork"), ("Beginner", "Fiverr")]) {
+        f.experience_level = level.to_string();
+        f.platform = platform.to_string();
+    }
+
+    let graph = build_directed_graph(&freelancers, is_mentorship);
+    assert_eq!(graph, vec![vec![1], vec![], vec![]]);
+    assert!(!graph[1].contains(&0));
+}
+
+/// Tests that a 3-cycle is one SCC while a chain is three SCCs but one weak component
+#[test]
+fn test_strongly_and_weakly_connected_components() {
+    let cycle = vec![vec![1], vec![2], vec![0]];
+    assert_eq!(strongly_connected_components(&cycle), vec![vec![0, 1, 2]]);
+    assert_eq!(weakly_connected_components(&cycle), vec![vec![0, 1, 2]]);
+
+    let chain = vec![vec![1], vec![2], vec![]];
+    assert_eq!(strongly_connected_components(&chain), vec![vec![0], vec![1], vec![2]]);
+    assert_eq!(weakly_connected_components(&chain), vec![vec![0, 1, 2]]);
+
+    // A cycle feeding into a separate pair that points back at itself, plus an isolated node
+    let mixed = vec![vec![1], vec![0, 2], vec![3], vec![2], vec![]];
+    assert_eq!(strongly_connected_components(&mixed), vec![vec![0, 1], vec![2, 3], vec![4]]);
+    assert_eq!(weakly_connected_components(&mixed), vec![vec![0, 1, 2, 3], vec![4]]);
+}
+
+/// Tests that Louvain splits two cliques joined by one weak edge, which connected components merge
+#[test]
+fn test_louvain_communities() {
+    let mut weighted_adj = vec![Vec::new(); 8];
+    for group in [0..4, 4..8] {
+        for a in group.clone() {
+            for b in group.clone().filter(|&b| b != a) {
+                weighted_adj[a].push((b, 1.0));
+            }
+        }
+    }
+    weighted_adj[3].push((4, 0.1));
+    weighted_adj[4].push((3, 0.1));
+
+    assert_eq!(find_connected_components(&to_unweighted(&weighted_adj)).len(), 1);
+    for seed in [0, 1, 42] {
+        let communities = louvain_communities(&weighted_adj, seed);
+        assert_eq!(communities, vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]]);
+    }
+    assert_eq!(louvain_communities(&weighted_adj, 7), louvain_communities(&weighted_adj, 7));
+    assert_eq!(louvain_communities(&[Vec::new(), Vec::new()], 0), vec![vec![0], vec![1]]);
+}
+
+/// Tests that a 4-clique survives k = 3 while its pendant chain is pruned
+#[test]
+fn test_k_core() {
+    // Clique 0-1-2-3, with 4 hanging off 3 and 5 hanging off 4
+    let edges = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3), (3, 4), (4, 5)];
+    let mut adj_list = vec![Vec::new(); 6];
+    for &(a, b) in &edges {
+        adj_list[a].push(b);
+        adj_list[b].push(a);
+    }
+
+    assert_eq!(k_core(&adj_list, 0), vec![0, 1, 2, 3, 4, 5]);
+    assert_eq!(k_core(&adj_list, 1), vec![0, 1, 2, 3, 4, 5]);
+    // Removing 5 leaves 4 with one neighbor, so the whole chain goes
+    assert_eq!(k_core(&adj_list, 2), vec![0, 1, 2, 3]);
+    assert_eq!(k_core(&adj_list, 3), vec![0, 1, 2, 3]);
+    assert!(k_core(&adj_list, 4).is_empty());
+}
+
+/// Tests shortest paths along a path graph, across components and with bad indices
+#[test]
+fn test_shortest_path() {
+    // Path 0-1-2-3 plus the separate edge 4-5
+    let adj_list = vec![vec![1], vec![0, 2], vec![1, 3], vec![2], vec![5], vec![4]];
+
+    let path = shortest_path(&adj_list, 0, 3).unwrap();
+    assert_eq!(path, vec![0, 1, 2, 3]);
+    assert_eq!(path.len() - 1, 3);
+    assert_eq!(shortest_path(&adj_list, 3, 1), Some(vec![3, 2, 1]));
+    assert_eq!(shortest_path(&adj_list, 2, 2), Some(vec![2]));
+    assert_eq!(shortest_path(&adj_list, 0, 5), None);
+    assert_eq!(shortest_path(&adj_list, 0, 6), None);
+}
+
+/// Tests that the adjacency matrix is symmetric and mirrors the adjacency list
+#[test]
+fn test_to_adjacency_matrix() {
+    let graph = build_collaboration_graph(&create_test_freelancers());
+    let matrix = to_adjacency_matrix(&graph);
+
+    assert_eq!(matrix.dim(), (graph.len(), graph.len()));
+    assert_eq!(matrix, matrix.t());
+    for i in 0..graph.len() {
+        for j in 0..graph.len() {
+            assert_eq!(matrix[[i, j]] == 1.0, graph[i].contains(&j));
+        }
+    }
+
+    let weighted = build_weighted_collaboration_graph(&create_test_freelancers());
+    let weights = to_weighted_adjacency_matrix(&weighted);
+    assert_eq!(weights.mapv(|w| if w > 0.0 { 1.0 } else { 0.0 }), matrix);
+}
+
+/// Tests that the parallel graph build matches the sequential one exactly
+#[test]
+fn test_build_collaboration_graph_parallel() {
+    for freelancers in [create_test_freelancers(), super::data_loader::generate_synthetic_freelancers(300, 5), Vec::new()] {
+        assert_eq!(build_collaboration_graph_parallel(&freelancers), build_collaboration_graph(&freelancers));
+    }
+}
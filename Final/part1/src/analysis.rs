@@ -1,9 +1,47 @@
-/// Module for analyzing freelancer data and generating insights.
-/// Provides functions for statistical analysis and data visualization.
+//! Module for analyzing freelancer data and generating insights.
+//! Provides functions for statistical analysis and data visualization.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use ndarray::Array2;
 use plotters::prelude::*;
-use super::data_loader::Freelancer;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use super::data_loader::{ExperienceLevel, Freelancer};
+#[cfg(test)]
+use super::data_loader::FreelancerBuilder;
+use super::algorithms::MergeStep;
+
+/// Formatting options for the printed analysis reports.
+///
+/// # Fields
+/// `abbreviate_money` - Show amounts of $1000 or more with k/M suffixes, e.g. "$5.0k"
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReportConfig {
+    pub abbreviate_money: bool,
+}
+
+impl ReportConfig {
+    /// Formats a dollar amount according to this configuration.
+    ///
+    /// Amounts under $1000 always keep two decimals. With `abbreviate_money`, larger
+    /// amounts use one decimal and a `k` (thousands) or `M` (millions) suffix.
+    ///
+    /// # Arguments: `amount` - Amount in USD
+    ///
+    /// # Returns: `String` - Formatted amount such as "$250.00", "$5.0k" or "$1.2M"
+    pub fn format_money(&self, amount: f32) -> String {
+        let sign = if amount < 0.0 { "-" } else { "" };
+        let magnitude = amount.abs();
+        if !self.abbreviate_money || magnitude < 1000.0 {
+            format!("${:.2}", amount)
+        } else if magnitude < 1_000_000.0 {
+            format!("{}${:.1}k", sign, magnitude / 1000.0)
+        } else {
+            format!("{}${:.1}M", sign, magnitude / 1_000_000.0)
+        }
+    }
+}
 
 /// Analyzes performance metrics for each cluster of freelancers.
 /// 
@@ -15,44 +53,177 @@ use super::data_loader::Freelancer;
 /// Prints analysis results including:
 /// Number of members in each cluster
 /// Average earnings per cluster
-/// Average hourly rate per cluster
-
+/// Average hourly rate per cluster, with a bootstrap 95% confidence interval
 pub fn analyze_cluster_performance(clusters: &[Vec<usize>], freelancers: &[Freelancer]) {
-    for (cluster_id, member_indices) in clusters.iter().enumerate() {
-        let mut total_earnings = 0.0;
-        let mut total_hourly = 0.0;
-        let mut count = 0;
-
-        // Calculate totals
-        for &index in member_indices {
-            if let Some(freelancer) = freelancers.get(index) {
-                total_earnings += freelancer.earnings_usd;
-                total_hourly += freelancer.hourly_rate;
-                count += 1;
+    analyze_cluster_performance_with_config(clusters, freelancers, &ReportConfig::default());
+}
+
+/// Aggregate performance figures of one cluster.
+///
+/// # Fields
+/// `cluster_id` - Position of the cluster in the input, starting at 0
+/// `member_count` - Number of members found in the freelancer data
+/// `avg_earnings` - Average earnings in USD, 0.0 for an empty cluster
+/// `avg_hourly_rate` - Average hourly rate in USD, 0.0 for an empty cluster
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClusterPerformance {
+    pub cluster_id: usize,
+    pub member_count: usize,
+    pub avg_earnings: f32,
+    pub avg_hourly_rate: f32,
+}
+
+/// Computes the member count and average earnings and hourly rate of each cluster.
+///
+/// Indices outside `freelancers` are ignored.
+///
+/// # Arguments: `clusters` - Clusters of freelancer indices, `freelancers` - Slice of Freelancer structs
+///
+/// # Returns: `Vec<ClusterPerformance>` - One entry per cluster, in cluster order
+pub fn cluster_performance(clusters: &[Vec<usize>], freelancers: &[Freelancer]) -> Vec<ClusterPerformance> {
+    clusters.iter()
+        .enumerate()
+        .map(|(cluster_id, member_indices)| {
+            let mut total_earnings = 0.0;
+            let mut total_hourly = 0.0;
+            let mut count = 0;
+
+            // Calculate totals
+            for &index in member_indices {
+                if let Some(freelancer) = freelancers.get(index) {
+                    total_earnings += freelancer.earnings_usd;
+                    total_hourly += freelancer.hourly_rate;
+                    count += 1;
+                }
             }
-        }
 
-        // Calculate averages
-        let avg_earnings = if count > 0 {
-            total_earnings / count as f32
-        } else {
-            0.0
-        };
+            // Calculate averages
+            let (avg_earnings, avg_hourly_rate) = if count > 0 {
+                (total_earnings / count as f32, total_hourly / count as f32)
+            } else {
+                (0.0, 0.0)
+            };
+            ClusterPerformance { cluster_id, member_count: count, avg_earnings, avg_hourly_rate }
+        })
+        .collect()
+}
 
-        let avg_hourly = if count > 0 {
-            total_hourly / count as f32
-        } else {
-            0.0
-        };
+/// Writes per-cluster performance results to a CSV file for spreadsheets.
+///
+/// The header is always written, so an empty slice produces a header-only file.
+///
+/// # Arguments: `path` - Output CSV file, `performance` - Results from `cluster_performance`
+///
+/// # Returns: `Result<(), Box<dyn std::error::Error>>` - Success or error writing the file
+pub fn export_cluster_performance_csv(path: &str, performance: &[ClusterPerformance]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = csv::Writer::from_path(path)?;
+    writer.write_record(["cluster_id", "member_count", "avg_earnings", "avg_hourly_rate"])?;
+    for row in performance {
+        writer.write_record([
+            row.cluster_id.to_string(),
+            row.member_count.to_string(),
+            row.avg_earnings.to_string(),
+            row.avg_hourly_rate.to_string(),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
 
+/// Analyzes performance metrics for each cluster, formatting amounts with `config`.
+///
+/// # Arguments: `clusters` - Clusters of freelancer indices, `freelancers` - Slice of Freelancer structs,
+/// `config` - Report formatting options
+pub fn analyze_cluster_performance_with_config(clusters: &[Vec<usize>], freelancers: &[Freelancer], config: &ReportConfig) {
+    for (performance, member_indices) in cluster_performance(clusters, freelancers).iter().zip(clusters) {
         // Print results
-        println!("Cluster {} Analysis:", cluster_id + 1);
-        println!("- Members: {}", count);
-        println!("- Average Earnings: ${:.2}", avg_earnings);
-        println!("- Average Hourly Rate: ${:.2}\n", avg_hourly);
+        println!("Cluster {} Analysis:", performance.cluster_id + 1);
+        println!("- Members: {}", performance.member_count);
+        println!("- Average Earnings: {}", config.format_money(performance.avg_earnings));
+        println!("- Average Hourly Rate: {}", config.format_money(performance.avg_hourly_rate));
+        let rates: Vec<f32> = member_indices.iter()
+            .filter_map(|&index| freelancers.get(index))
+            .map(|f| f.hourly_rate)
+            .collect();
+        let (ci_low, ci_high) = bootstrap_mean_ci(&rates, BOOTSTRAP_ITERATIONS, 0.05, 42);
+        println!("- 95% CI for Hourly Rate: ${:.2} - ${:.2}", ci_low, ci_high);
+        println!("- Rate Distribution: {}\n", rate_sparkline(member_indices, freelancers));
     }
 }
 
+/// Number of resamples used for the per-cluster rate confidence intervals.
+const BOOTSTRAP_ITERATIONS: usize = 1000;
+
+/// Computes a percentile bootstrap confidence interval for the mean.
+/// 
+/// Draws `iterations` resamples of `values` with replacement, takes the mean of
+/// each, and returns the `alpha / 2` and `1 - alpha / 2` quantiles of those means.
+/// The same seed always yields the same interval.
+/// 
+/// # Arguments: `values` - Sample, `iterations` - Number of resamples, `alpha` - Significance level (0.05 for a 95% interval),
+/// `seed` - Random seed
+/// 
+/// # Returns: `(f64, f64)` - Lower and upper bound, or `(NaN, NaN)` if `values` is empty or `iterations` is 0
+pub fn bootstrap_mean_ci(values: &[f32], iterations: usize, alpha: f64, seed: u64) -> (f64, f64) {
+    if values.is_empty() || iterations == 0 {
+        return (f64::NAN, f64::NAN);
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut means: Vec<f64> = (0..iterations)
+        .map(|_| {
+            let sum: f64 = (0..values.len())
+                .map(|_| values[rng.gen_range(0..values.len())] as f64)
+                .sum();
+            sum / values.len() as f64
+        })
+        .collect();
+    means.sort_by(|a, b| a.total_cmp(b));
+
+    let alpha = alpha.clamp(0.0, 1.0);
+    let quantile = |q: f64| {
+        let rank = q * (means.len() - 1) as f64;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        means[lower] + (means[upper] - means[lower]) * (rank - lower as f64)
+    };
+    (quantile(alpha / 2.0), quantile(1.0 - alpha / 2.0))
+}
+
+/// Renders the sorted hourly rates of a cluster as a unicode sparkline.
+/// 
+/// Each member becomes one block character, scaled between the cluster's lowest
+/// and highest rate. A cluster where every rate is equal renders as a flat line.
+/// Indices outside `freelancers` are skipped.
+/// 
+/// # Arguments: `cluster` - Indices of the cluster members, `freelancers` - Slice of Freelancer structs
+/// 
+/// # Returns: `String` - One block character per member, lowest rate first
+pub fn rate_sparkline(cluster: &[usize], freelancers: &[Freelancer]) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let mut rates: Vec<f32> = cluster.iter()
+        .filter_map(|&idx| freelancers.get(idx))
+        .map(|f| f.hourly_rate)
+        .collect();
+    rates.sort_by(|a, b| a.total_cmp(b));
+
+    let min = rates.first().copied().unwrap_or(0.0);
+    let max = rates.last().copied().unwrap_or(0.0);
+    let span = max - min;
+
+    rates.iter()
+        .map(|&rate| {
+            let level = if span > 0.0 {
+                ((rate - min) / span * (BLOCKS.len() - 1) as f32).round() as usize
+            } else {
+                0
+            };
+            BLOCKS[level.min(BLOCKS.len() - 1)]
+        })
+        .collect()
+}
+
 /// Analyzes the profile characteristics of each cluster.
 /// 
 /// # Arguments
@@ -65,10 +236,11 @@ pub fn analyze_cluster_performance(clusters: &[Vec<usize>], freelancers: &[Freel
 ///Platform distribution
 ///Client Region distribution
 ///Experience Level distribution
+///Number of distinct values of each attribute
 pub fn analyze_cluster_profiles(clusters: &[Vec<usize>], freelancers: &[Freelancer]) {
     for (cluster_id, member_indices) in clusters.iter().enumerate() {
         let mut attributes = HashMap::new();
-        let mut total_members = member_indices.len();
+        let total_members = member_indices.len();
         
         for &idx in member_indices {
             let f = &freelancers[idx];
@@ -83,9 +255,100 @@ pub fn analyze_cluster_profiles(clusters: &[Vec<usize>], freelancers: &[Freelanc
         print_dominant_attributes(&attributes, "Platform", total_members);
         print_dominant_attributes(&attributes, "Region", total_members);
         print_dominant_attributes(&attributes, "Experience", total_members);
+
+        let diversity = &cluster_diversity(std::slice::from_ref(member_indices), freelancers)[0];
+        println!(
+            "- Distinct Values: {} categories, {} platforms, {} regions, {} experience levels",
+            diversity.job_categories, diversity.platforms, diversity.regions, diversity.experience_levels
+        );
     }
 }
 
+/// Number of distinct attribute values within one cluster.
+///
+/// # Fields
+/// `job_categories` - Distinct job categories
+/// `platforms` - Distinct platforms
+/// `regions` - Distinct client regions
+/// `experience_levels` - Distinct experience levels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClusterDiversity {
+    pub job_categories: usize,
+    pub platforms: usize,
+    pub regions: usize,
+    pub experience_levels: usize,
+}
+
+/// Counts the distinct attribute values in each cluster.
+///
+/// A cluster with one value of each attribute is homogeneous; many distinct values
+/// indicate a loose grouping.
+///
+/// # Arguments: `clusters` - Clusters of freelancer indices, `freelancers` - Slice of Freelancer structs
+///
+/// # Returns: `Vec<ClusterDiversity>` - One entry per cluster, in cluster order
+pub fn cluster_diversity(clusters: &[Vec<usize>], freelancers: &[Freelancer]) -> Vec<ClusterDiversity> {
+    clusters.iter()
+        .map(|members| {
+            let distinct = |field: fn(&Freelancer) -> &str| {
+                members.iter().map(|&i| field(&freelancers[i])).collect::<HashSet<_>>().len()
+            };
+            ClusterDiversity {
+                job_categories: distinct(|f| &f.job_category),
+                platforms: distinct(|f| &f.platform),
+                regions: distinct(|f| &f.client_region),
+                experience_levels: distinct(|f| &f.experience_level),
+            }
+        })
+        .collect()
+}
+
+/// Summarizes each cluster as one row of numeric features for cluster-level models.
+///
+/// The columns are the cluster size, mean hourly rate, mean earnings, mean job success
+/// rate and the code of the most common job category. Category codes are positions in
+/// the alphabetically sorted job categories of all `freelancers`; ties between equally
+/// common categories pick the alphabetically first. Empty clusters get NaN means and code.
+///
+/// # Arguments: `clusters` - Clusters of freelancer indices, `freelancers` - Slice of Freelancer structs
+///
+/// # Returns: `(Array2<f64>, Vec<String>)` - One row per cluster in cluster order, and the column names
+pub fn cluster_feature_vectors(clusters: &[Vec<usize>], freelancers: &[Freelancer]) -> (Array2<f64>, Vec<String>) {
+    let names: Vec<String> = ["size", "mean_hourly_rate", "mean_earnings_usd", "mean_job_success_rate", "dominant_category_code"]
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    let mut categories: Vec<&str> = freelancers.iter().map(|f| f.job_category.as_str()).collect();
+    categories.sort_unstable();
+    categories.dedup();
+
+    let mut features = Array2::from_elem((clusters.len(), names.len()), f64::NAN);
+    for (mut row, members) in features.rows_mut().into_iter().zip(clusters) {
+        row[0] = members.len() as f64;
+        if members.is_empty() {
+            continue;
+        }
+        let mean = |field: fn(&Freelancer) -> f32| {
+            members.iter().map(|&i| field(&freelancers[i]) as f64).sum::<f64>() / members.len() as f64
+        };
+        row[1] = mean(|f| f.hourly_rate);
+        row[2] = mean(|f| f.earnings_usd);
+        row[3] = mean(|f| f.job_success_rate);
+
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for &i in members {
+            *counts.entry(freelancers[i].job_category.as_str()).or_insert(0) += 1;
+        }
+        let dominant = counts.into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(category, _)| category);
+        if let Some(code) = dominant.and_then(|category| categories.binary_search(&category).ok()) {
+            row[4] = code as f64;
+        }
+    }
+    (features, names)
+}
+
 /// Prints the dominant attributes for a given category in a cluster.
 /// 
 /// # Arguments
@@ -106,32 +369,35 @@ fn print_dominant_attributes(attributes: &HashMap<(&str, String), usize>, catego
     }
 }
 
+/// Smallest top of the rate axis in `plot_cluster_experience_rates`, in USD.
+const MIN_CHART_RATE: f32 = 1.0;
+
 /// Plots the hourly rates by experience level for each cluster.
 /// 
 /// # Arguments
 /// `clusters` - Vector of clusters, where each cluster is a vector of freelancer indices
 /// `freelancers` - Slice of Freelancer structs to analyze
+/// `path` - Output PNG file, e.g. "cluster_experience_rates.png"
 /// 
 /// # Returns
 /// `Result<(), Box<dyn Error>>` - Success or error status
 /// 
 /// # Output
-/// Generates a bar chart saved as "cluster_experience_rates.png" showing:
+/// Generates a bar chart saved at `path` showing:
 /// Hourly rates grouped by experience level
 /// Different colors for each experience level
 /// Cluster IDs on x-axis
 /// Average hourly rates on y-axis
-
-
 pub fn plot_cluster_experience_rates(
     clusters: &[Vec<usize>],
     freelancers: &[Freelancer],
+    path: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // 1. Prepare data structure
     let mut cluster_data = Vec::new();
-    let experience_levels = ["Beginner", "Intermediate", "Expert"];
+    let experience_levels = ExperienceLevel::ALL;
     let colors = [
-        RGBColor(255, 0, 0),    // Red for Beginner
+        RGBColor(255, 0, 0),    // Red for Entry Level (Beginner)
         RGBColor(0, 255, 0),    // Green for Intermediate
         RGBColor(0, 0, 255),    // Blue for Expert
     ];
@@ -140,17 +406,19 @@ pub fn plot_cluster_experience_rates(
         let mut exp_rates = HashMap::new();
         let mut counts = HashMap::new();
 
-        // Calculate averages per experience level
+        // Calculate averages per experience level; unrecognized levels are skipped
         for &member_idx in members {
             let f = &freelancers[member_idx];
-            *exp_rates.entry(f.experience_level.as_str()).or_insert(0.0) += f.hourly_rate;
-            *counts.entry(f.experience_level.as_str()).or_insert(0) += 1;
+            if let Ok(level) = f.experience_level.parse::<ExperienceLevel>() {
+                *exp_rates.entry(level).or_insert(0.0) += f.hourly_rate;
+                *counts.entry(level).or_insert(0) += 1;
+            }
         }
 
         let mut cluster_rates = Vec::new();
         for exp in &experience_levels {
-            let avg = counts.get(*exp)
-                .and_then(|&c| if c > 0 { Some(exp_rates[*exp] / c as f32) } else { None })
+            let avg = counts.get(exp)
+                .and_then(|&c| if c > 0 { Some(exp_rates[exp] / c as f32) } else { None })
                 .unwrap_or(0.0);
             cluster_rates.push(avg);
         }
@@ -159,12 +427,16 @@ pub fn plot_cluster_experience_rates(
     }
 
     // 2. Create the chart with continuous x-axis
-    let root = BitMapBackend::new("cluster_experience_rates.png", (1024, 768)).into_drawing_area();
+    let root = BitMapBackend::new(path, (1024, 768)).into_drawing_area();
     root.fill(&WHITE)?;
 
+    // Without clusters, or when every average is zero (e.g. only experience levels
+    // outside the list above), keep the y-axis at least one dollar tall
     let max_rate = cluster_data.iter()
-        .flat_map(|(_, rates)| rates.iter())
-        .fold(f32::NAN, |a, &b| a.max(b)) * 1.1;
+        .flat_map(|(_, rates)| rates.iter().copied())
+        .filter(|rate| rate.is_finite())
+        .fold(0.0, f32::max)
+        .max(MIN_CHART_RATE) * 1.1;
 
     let mut chart = ChartBuilder::on(&root)
         .caption("Hourly Rates by Experience Level per Cluster", ("sans-serif", 30))
@@ -172,20 +444,19 @@ pub fn plot_cluster_experience_rates(
         .x_label_area_size(40)
         .y_label_area_size(50)
         .build_cartesian_2d(
-            0.0..clusters.len() as f64,  // Continuous x-axis
+            0.0..clusters.len().max(1) as f64,  // Continuous x-axis
             0.0..max_rate as f64         // Continuous y-axis
         )?;
 
     chart.configure_mesh()
         .x_desc("Cluster ID")
         .y_desc("Average Hourly Rate (USD)")
-        .bold_line_style(&BLACK.mix(0.2))
+        .bold_line_style(BLACK.mix(0.2))
         .x_labels(15)
         .draw()?;
 
     // 3. Draw grouped bars with proper coordinate types
     let bar_width = 0.15; 
-    let group_width = bar_width * 3.0; 
     
     for (exp_idx, exp) in experience_levels.iter().enumerate() {
         let x_offset = (exp_idx as f64 - 1.0) * bar_width;
@@ -203,7 +474,7 @@ pub fn plot_cluster_experience_rates(
                     colors[exp_idx].filled(),
                 )
             })
-        )?.label(*exp)
+        )?.label(exp.label())
           .legend(move |(x, y)| {
               Rectangle::new(
                   [(x, y - 5), (x + 20, y + 5)],
@@ -215,10 +486,793 @@ pub fn plot_cluster_experience_rates(
     // 4. Add legend and finalize
     chart.configure_series_labels()
         .position(SeriesLabelPosition::UpperRight)
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+
+    root.present()?;
+    Ok(())
+}
+
+/// Draws a histogram of hourly rates with equal-width bins.
+///
+/// The bins span the smallest to the largest rate. When every rate is equal the
+/// span is widened to $1 so all freelancers fall into the first bin.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs, `bins` - Number of bins, `path` - Output PNG file
+///
+/// # Returns: `Result<(), Box<dyn Error>>` - Success or error during plot generation
+///
+/// # Errors
+/// Returns error if `bins` is 0 or no freelancer has a finite hourly rate
+pub fn plot_hourly_rate_histogram(freelancers: &[Freelancer], bins: usize, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if bins == 0 {
+        return Err("histogram needs at least one bin".into());
+    }
+    let rates: Vec<f64> = freelancers.iter()
+        .map(|f| f.hourly_rate as f64)
+        .filter(|rate| rate.is_finite())
+        .collect();
+    if rates.is_empty() {
+        return Err("no hourly rates to plot".into());
+    }
+
+    let min_rate = rates.iter().copied().fold(f64::INFINITY, f64::min);
+    let max_rate = rates.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let span = if max_rate > min_rate { max_rate - min_rate } else { 1.0 };
+    let width = span / bins as f64;
+    let mut counts = vec![0usize; bins];
+    for rate in &rates {
+        let bin = (((rate - min_rate) / width) as usize).min(bins - 1);
+        counts[bin] += 1;
+    }
+    let max_count = counts.iter().copied().max().unwrap_or(0);
+
+    let root = BitMapBackend::new(path, (800, 600)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Hourly Rate Distribution", ("sans-serif", 30))
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(min_rate..min_rate + span, 0usize..max_count + 1)?;
+
+    chart.configure_mesh()
+        .x_desc("Hourly Rate (USD)")
+        .y_desc("Freelancers")
+        .bold_line_style(BLACK.mix(0.2))
         .draw()?;
 
+    chart.draw_series(counts.iter().enumerate().map(|(i, &count)| {
+        let left = min_rate + i as f64 * width;
+        Rectangle::new([(left, 0), (left + width, count)], BLUE.mix(0.7).filled())
+    }))?;
+
     root.present()?;
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Returns every freelancer's earnings sorted ascending.
+fn sorted_earnings(freelancers: &[Freelancer]) -> Vec<f64> {
+    let mut earnings: Vec<f64> = freelancers.iter().map(|f| f.earnings_usd as f64).collect();
+    earnings.sort_by(|a, b| a.total_cmp(b));
+    earnings
+}
+
+/// Computes points of the Lorenz curve of earnings.
+///
+/// Freelancers are sorted by earnings ascending; each point pairs the fraction of
+/// freelancers included so far with their share of total earnings. Population
+/// fractions are evenly spaced from 0 to 1, so the curve always starts at (0, 0)
+/// and ends at (1, 1). With zero total earnings the curve is the equality line.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs, `points` - Number of points (at least 2 are returned)
+///
+/// # Returns: `Vec<(f64, f64)>` - (cumulative population fraction, cumulative earnings fraction) pairs
+pub fn lorenz_curve(freelancers: &[Freelancer], points: usize) -> Vec<(f64, f64)> {
+    let earnings = sorted_earnings(freelancers);
+    let n = earnings.len();
+    let total: f64 = earnings.iter().sum();
+
+    let mut cumulative = vec![0.0; n + 1];
+    for (i, value) in earnings.iter().enumerate() {
+        cumulative[i + 1] = cumulative[i] + value;
+    }
+
+    let points = points.max(2);
+    (0..points)
+        .map(|i| {
+            let population = i as f64 / (points - 1) as f64;
+            let included = (population * n as f64).round() as usize;
+            let share = if total > 0.0 { cumulative[included] / total } else { population };
+            (population, share)
+        })
+        .collect()
+}
+
+/// Computes the Gini coefficient of earnings.
+///
+/// Uses the exact formula on sorted earnings `x_1 ≤ … ≤ x_n`:
+/// `G = 2·Σ i·x_i / (n·Σ x_i) - (n + 1) / n`.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs
+///
+/// # Returns: `f64` - 0.0 for perfect equality up to `(n - 1) / n` when one freelancer earns everything;
+/// 0.0 for empty input or zero total earnings
+pub fn gini_coefficient(freelancers: &[Freelancer]) -> f64 {
+    let earnings = sorted_earnings(freelancers);
+    let n = earnings.len() as f64;
+    let total: f64 = earnings.iter().sum();
+    if earnings.is_empty() || total <= 0.0 {
+        return 0.0;
+    }
+
+    let weighted: f64 = earnings.iter()
+        .enumerate()
+        .map(|(i, value)| (i + 1) as f64 * value)
+        .sum();
+    2.0 * weighted / (n * total) - (n + 1.0) / n
+}
+
+/// Plots the earnings Lorenz curve against the line of perfect equality.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs, `path` - Output PNG file
+///
+/// # Returns: `Result<(), Box<dyn Error>>` - Success or error during plot generation
+pub fn plot_lorenz_curve(freelancers: &[Freelancer], path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let curve = lorenz_curve(freelancers, 101);
+    let caption = format!("Earnings Lorenz Curve (Gini {:.3})", gini_coefficient(freelancers));
+
+    let root = BitMapBackend::new(path, (800, 800)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(caption, ("sans-serif", 30))
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(0.0..1.0, 0.0..1.0)?;
+
+    chart.configure_mesh()
+        .x_desc("Cumulative Share of Freelancers")
+        .y_desc("Cumulative Share of Earnings")
+        .bold_line_style(BLACK.mix(0.2))
+        .draw()?;
+
+    chart.draw_series(LineSeries::new(vec![(0.0, 0.0), (1.0, 1.0)], BLACK.mix(0.5)))?
+        .label("Perfect equality")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLACK.mix(0.5)));
+
+    chart.draw_series(LineSeries::new(curve, BLUE.stroke_width(2)))?
+        .label("Lorenz curve")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+
+    chart.configure_series_labels()
+        .position(SeriesLabelPosition::UpperLeft)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+
+    root.present()?;
+    Ok(())
+}
+
+/// Plots precision against recall for the points returned by `edge_prediction_pr`.
+///
+/// Points with an undefined recall are skipped. Each point is labelled with its threshold.
+///
+/// # Arguments: `curve` - `(threshold, precision, recall)` points, `path` - Output PNG file
+///
+/// # Returns: `Result<(), Box<dyn Error>>` - Success or error during plot generation
+pub fn plot_precision_recall(curve: &[(f32, f64, f64)], path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut points: Vec<(f64, f64, f32)> = curve.iter()
+        .filter(|(_, precision, recall)| precision.is_finite() && recall.is_finite())
+        .map(|&(threshold, precision, recall)| (recall, precision, threshold))
+        .collect();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let root = BitMapBackend::new(path, (800, 800)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Edge Prediction Precision/Recall", ("sans-serif", 30))
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(0.0..1.05, 0.0..1.05)?;
+
+    chart.configure_mesh()
+        .x_desc("Recall")
+        .y_desc("Precision")
+        .bold_line_style(BLACK.mix(0.2))
+        .draw()?;
+
+    chart.draw_series(LineSeries::new(points.iter().map(|&(r, p, _)| (r, p)), BLUE.stroke_width(2)))?;
+    chart.draw_series(points.iter().map(|&(r, p, threshold)| {
+        EmptyElement::at((r, p))
+            + Circle::new((0, 0), 4, BLUE.filled())
+            + Text::new(format!("{:.2}", threshold), (6, -14), ("sans-serif", 14))
+    }))?;
+
+    root.present()?;
+    Ok(())
+}
+
+/// Summary statistics of one group of freelancers.
+///
+/// # Fields
+/// `count` - Number of freelancers in the group
+/// `mean_rate` - Mean hourly rate in USD
+/// `mean_earnings` - Mean earnings in USD
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GroupStats {
+    pub count: usize,
+    pub mean_rate: f64,
+    pub mean_earnings: f64,
+}
+
+/// Groups freelancers by an arbitrary key and summarizes each group.
+///
+/// Keys can be composite, e.g. `|f| (f.platform.clone(), f.experience_level.clone())`,
+/// so reports by platform, region or any combination are special cases.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs, `key` - Function computing each freelancer's group
+///
+/// # Returns: `HashMap<K, GroupStats>` - Count, mean rate and mean earnings per group
+pub fn group_by<K, F>(freelancers: &[Freelancer], key: F) -> HashMap<K, GroupStats>
+where
+    K: Eq + Hash,
+    F: Fn(&Freelancer) -> K,
+{
+    let mut sums: HashMap<K, (usize, f64, f64)> = HashMap::new();
+    for freelancer in freelancers {
+        let (count, rate, earnings) = sums.entry(key(freelancer)).or_insert((0, 0.0, 0.0));
+        *count += 1;
+        *rate += freelancer.hourly_rate as f64;
+        *earnings += freelancer.earnings_usd as f64;
+    }
+
+    sums.into_iter()
+        .map(|(group, (count, rate, earnings))| {
+            let n = count as f64;
+            (group, GroupStats { count, mean_rate: rate / n, mean_earnings: earnings / n })
+        })
+        .collect()
+}
+
+/// Computes multiplicative rate adjustment factors per client region.
+///
+/// Each factor is the region's mean hourly rate divided by the global mean, so a
+/// region priced like the dataset as a whole gets a factor of 1.0.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs
+///
+/// # Returns: `HashMap<String, f64>` - Adjustment factor per client region (empty if the global mean is not positive)
+pub fn region_adjustment_factors(freelancers: &[Freelancer]) -> HashMap<String, f64> {
+    if freelancers.is_empty() {
+        return HashMap::new();
+    }
+    let global_mean = freelancers.iter().map(|f| f.hourly_rate as f64).sum::<f64>() / freelancers.len() as f64;
+    if global_mean <= 0.0 {
+        return HashMap::new();
+    }
+    group_by(freelancers, |f| f.client_region.clone())
+        .into_iter()
+        .map(|(region, stats)| (region, stats.mean_rate / global_mean))
+        .collect()
+}
+
+/// Divides each freelancer's rate by their region's adjustment factor.
+///
+/// Regions missing from `factors` are left unadjusted.
+///
+/// # Arguments: `freelancers` - Slice of Freelancer structs, `factors` - Output of `region_adjustment_factors`
+///
+/// # Returns: `Vec<f64>` - Region-adjusted hourly rate per freelancer, in input order
+pub fn region_adjusted_rates(freelancers: &[Freelancer], factors: &HashMap<String, f64>) -> Vec<f64> {
+    freelancers.iter()
+        .map(|f| f.hourly_rate as f64 / factors.get(&f.client_region).copied().unwrap_or(1.0))
+        .collect()
+}
+
+/// Computes a quantile of sorted values using linear interpolation between ranks.
+fn quantile_sorted(sorted: &[f32], q: f32) -> f32 {
+    let rank = q * (sorted.len() - 1) as f32;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f32)
+}
+
+/// Computes the inter-quartile-range outlier fences for a set of values.
+/// 
+/// # Arguments: `values` - Values to analyze, in any order
+/// 
+/// # Returns: `(f32, f32)` - Lower fence (Q1 - 1.5·IQR) and upper fence (Q3 + 1.5·IQR),
+/// or `(NaN, NaN)` if `values` is empty
+pub fn iqr_bounds(values: &[f32]) -> (f32, f32) {
+    if values.is_empty() {
+        return (f32::NAN, f32::NAN);
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    let q1 = quantile_sorted(&sorted, 0.25);
+    let q3 = quantile_sorted(&sorted, 0.75);
+    let iqr = q3 - q1;
+    (q1 - 1.5 * iqr, q3 + 1.5 * iqr)
+}
+
+/// Computes a percentile using linear interpolation between the closest ranks.
+/// 
+/// NaN values are ignored.
+/// 
+/// # Arguments: `values` - Values in any order, `p` - Percentile between 0 and 100
+/// 
+/// # Returns: `Option<f64>` - The percentile, or `None` if there are no values or `p` is outside 0-100
+pub fn percentile(values: &[f64], p: f64) -> Option<f64> {
+    if !(0.0..=100.0).contains(&p) {
+        return None;
+    }
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| !v.is_nan()).collect();
+    if sorted.is_empty() {
+        return None;
+    }
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64))
+}
+
+/// The 25th, 50th and 75th percentiles of a set of values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quartiles {
+    pub q1: f64,
+    pub median: f64,
+    pub q3: f64,
+}
+
+impl Quartiles {
+    /// Computes the quartiles with `percentile`.
+    /// 
+    /// # Returns: `Option<Quartiles>` - `None` if `values` has no non-NaN value
+    pub fn of(values: &[f64]) -> Option<Quartiles> {
+        Some(Quartiles {
+            q1: percentile(values, 25.0)?,
+            median: percentile(values, 50.0)?,
+            q3: percentile(values, 75.0)?,
+        })
+    }
+}
+
+/// Quartiles of hourly rate and earnings for one group of freelancers.
+/// 
+/// # Fields
+/// `hourly_rate` - Quartiles of the hourly rate in USD
+/// `earnings` - Quartiles of the earnings in USD
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClusterQuartiles {
+    pub hourly_rate: Quartiles,
+    pub earnings: Quartiles,
+}
+
+/// Computes hourly rate and earnings quartiles for a cluster.
+/// 
+/// Pass every index, e.g. `(0..freelancers.len()).collect::<Vec<_>>()`, for the whole dataset.
+/// 
+/// # Arguments: `cluster` - Indices of the cluster's members, `freelancers` - Slice of Freelancer structs
+/// 
+/// # Returns: `Option<ClusterQuartiles>` - Quartiles, or `None` for an empty cluster
+pub fn cluster_quartiles(cluster: &[usize], freelancers: &[Freelancer]) -> Option<ClusterQuartiles> {
+    let rates: Vec<f64> = cluster.iter().map(|&i| freelancers[i].hourly_rate as f64).collect();
+    let earnings: Vec<f64> = cluster.iter().map(|&i| freelancers[i].earnings_usd as f64).collect();
+    Some(ClusterQuartiles {
+        hourly_rate: Quartiles::of(&rates)?,
+        earnings: Quartiles::of(&earnings)?,
+    })
+}
+
+/// Finds the highest-earning members of a cluster.
+/// 
+/// Members with equal earnings keep their order in `cluster`.
+/// 
+/// # Arguments: `cluster` - Indices of the cluster's members, `freelancers` - Slice of Freelancer structs,
+/// `n` - Number of members to return
+/// 
+/// # Returns: `Vec<usize>` - Indices of at most `n` members, highest `earnings_usd` first
+pub fn top_earners(cluster: &[usize], freelancers: &[Freelancer], n: usize) -> Vec<usize> {
+    let mut members = cluster.to_vec();
+    members.sort_by(|&a, &b| freelancers[b].earnings_usd.total_cmp(&freelancers[a].earnings_usd));
+    members.truncate(n);
+    members
+}
+
+/// Finds freelancers whose value for a field falls outside the given bounds.
+/// 
+/// # Arguments
+/// `freelancers` - Slice of Freelancer structs to check
+/// `accessor` - Extracts the numeric field to check, e.g. `|f| f.earnings_usd`
+/// `bounds` - Inclusive (lower, upper) bounds, typically from `iqr_bounds`
+/// 
+/// # Returns: `Vec<usize>` - Indices of the freelancers outside the bounds
+pub fn find_field_outliers<F>(freelancers: &[Freelancer], accessor: F, bounds: (f32, f32)) -> Vec<usize>
+where
+    F: Fn(&Freelancer) -> f32,
+{
+    let (lower, upper) = bounds;
+    freelancers.iter()
+        .enumerate()
+        .filter(|(_, f)| {
+            let value = accessor(f);
+            value < lower || value > upper
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Computes the fraction of freelancers charging a lower hourly rate than the target.
+/// 
+/// # Arguments
+/// `freelancers` - Population to compare against (may include the target itself)
+/// `target` - Freelancer whose rate is ranked
+/// `same_category` - Only compare against freelancers in the target's job category
+/// 
+/// # Returns: `f64` - Fraction in [0, 1] of the compared freelancers with a strictly lower rate,
+/// or 0.0 if nobody is compared
+pub fn rate_percentile_rank(freelancers: &[Freelancer], target: &Freelancer, same_category: bool) -> f64 {
+    let peers: Vec<&Freelancer> = freelancers.iter()
+        .filter(|f| !same_category || f.job_category == target.job_category)
+        .collect();
+    if peers.is_empty() {
+        return 0.0;
+    }
+    let below = peers.iter().filter(|f| f.hourly_rate < target.hourly_rate).count();
+    below as f64 / peers.len() as f64
+}
+
+/// Renders agglomerative merge steps as a text dendrogram.
+///
+/// # Arguments: `steps` - Merge steps as returned by `agglomerative_linkage`
+///
+/// # Returns: `String` - One line per merge, listing the merged ids, new id, distance and size
+pub fn format_dendrogram(steps: &[MergeStep]) -> String {
+    // Leaves are numbered 0..n, and there are n - 1 merges
+    let n = steps.len() + 1;
+    let mut output = String::new();
+    for (k, step) in steps.iter().enumerate() {
+        output.push_str(&format!(
+            "Step {}: {} + {} -> {} (distance {:.2}, size {})\n",
+            k + 1, step.left, step.right, n + k, step.distance, step.size
+        ));
+    }
+    output
+}
+
+/// Creates a freelancer with the given numeric fields for unit testing
+#[cfg(test)]
+fn test_freelancer(id: u32, earnings_usd: f32, hourly_rate: f32, job_success_rate: f32) -> Freelancer {
+    FreelancerBuilder::new()
+        .id(id)
+        .experience_level("Expert")
+        .earnings_usd(earnings_usd)
+        .hourly_rate(hourly_rate)
+        .job_success_rate(job_success_rate)
+        .build()
+}
+
+/// Tests IQR fences and outlier detection on a dataset with one extreme earner
+#[test]
+fn test_iqr_outliers() {
+    let earnings = [1000.0, 1100.0, 1200.0, 1300.0, 1400.0, 50000.0];
+    let freelancers: Vec<Freelancer> = earnings.iter()
+        .enumerate()
+        .map(|(i, &e)| test_freelancer(i as u32, e, 50.0, 90.0))
+        .collect();
+
+    // Q1 = 1125, Q3 = 1375, IQR = 250
+    let (lower, upper) = iqr_bounds(&earnings);
+    assert!((lower - 750.0).abs() < 1e-3);
+    assert!((upper - 1750.0).abs() < 1e-3);
+
+    let outliers = find_field_outliers(&freelancers, |f| f.earnings_usd, (lower, upper));
+    assert_eq!(outliers, vec![5]);
+
+    let rates: Vec<f32> = freelancers.iter().map(|f| f.job_success_rate).collect();
+    assert!(find_field_outliers(&freelancers, |f| f.job_success_rate, iqr_bounds(&rates)).is_empty());
+}
+
+/// Tests that the sparkline has one character per member and spans the block range
+#[test]
+fn test_rate_sparkline() {
+    let freelancers: Vec<Freelancer> = [40.0, 10.0, 80.0, 25.0]
+        .iter()
+        .enumerate()
+        .map(|(i, &rate)| test_freelancer(i as u32, 0.0, rate, 90.0))
+        .collect();
+
+    let sparkline = rate_sparkline(&[0, 1, 2, 3], &freelancers);
+    assert_eq!(sparkline.chars().count(), 4);
+    assert!(sparkline.starts_with('▁'));
+    assert!(sparkline.ends_with('█'));
+
+    assert_eq!(rate_sparkline(&[1], &freelancers), "▁");
+    assert_eq!(rate_sparkline(&[], &freelancers), "");
+    assert_eq!(rate_sparkline(&[1, 99], &freelancers), "▁");
+}
+
+/// Tests percentile rank over the whole population and within a category
+#[test]
+fn test_rate_percentile_rank() {
+    let mut freelancers: Vec<Freelancer> = [10.0, 20.0, 30.0, 40.0, 50.0]
+        .iter()
+        .enumerate()
+        .map(|(i, &rate)| test_freelancer(i as u32, 0.0, rate, 90.0))
+        .collect();
+    freelancers[0].job_category = "Design".to_string();
+    freelancers[4].job_category = "Design".to_string();
+
+    // Three of five freelancers charge less than $40
+    let target = &freelancers[3];
+    assert!((rate_percentile_rank(&freelancers, target, false) - 0.6).abs() < 1e-9);
+    // Among Web Development (20, 30, 40) two of three charge less
+    assert!((rate_percentile_rank(&freelancers, target, true) - 2.0 / 3.0).abs() < 1e-9);
+}
+
+/// Tests that the Lorenz curve spans (0,0) to (1,1) and agrees with the Gini coefficient
+#[test]
+fn test_lorenz_curve_and_gini() {
+    let freelancers: Vec<Freelancer> = [400.0, 100.0, 300.0, 200.0]
+        .iter()
+        .enumerate()
+        .map(|(i, &e)| test_freelancer(i as u32, e, 50.0, 90.0))
+        .collect();
+
+    let curve = lorenz_curve(&freelancers, 5);
+    assert_eq!(curve.len(), 5);
+    assert_eq!(curve[0], (0.0, 0.0));
+    assert_eq!(curve[4], (1.0, 1.0));
+    // The poorest quarter holds 100 of 1000
+    assert!((curve[1].1 - 0.1).abs() < 1e-12);
+    assert!(curve.windows(2).all(|w| w[1].1 >= w[0].1));
+
+    // G = 2·(100 + 400 + 900 + 1600) / (4·1000) - 5/4 = 0.25
+    assert!((gini_coefficient(&freelancers) - 0.25).abs() < 1e-12);
+    let equal: Vec<Freelancer> = (0..3).map(|i| test_freelancer(i, 500.0, 50.0, 90.0)).collect();
+    assert!(gini_coefficient(&equal).abs() < 1e-12);
+}
+
+/// Tests that a large-sample bootstrap interval contains the true mean and is reproducible
+#[test]
+fn test_bootstrap_mean_ci() {
+    // 0..100 repeated ten times has mean 49.5
+    let values: Vec<f32> = (0..1000).map(|i| (i % 100) as f32).collect();
+
+    let (low, high) = bootstrap_mean_ci(&values, 500, 0.05, 7);
+    assert!(low < 49.5 && 49.5 < high);
+    assert!(high - low < 5.0);
+    assert_eq!(bootstrap_mean_ci(&values, 500, 0.05, 7), (low, high));
+
+    let (empty_low, empty_high) = bootstrap_mean_ci(&[], 500, 0.05, 7);
+    assert!(empty_low.is_nan() && empty_high.is_nan());
+}
+
+/// Tests diversity counts on a homogeneous and a heterogeneous cluster
+#[test]
+fn test_cluster_diversity() {
+    let mut freelancers: Vec<Freelancer> = (0..4).map(|i| test_freelancer(i, 1000.0, 50.0, 90.0)).collect();
+    freelancers[3].job_category = "Design".to_string();
+    freelancers[3].platform = "Fiverr".to_string();
+    freelancers[3].client_region = "Europe".to_string();
+    freelancers[2].experience_level = "Beginner".to_string();
+
+    let diversity = cluster_diversity(&[vec![0, 1], vec![1, 2, 3]], &freelancers);
+    assert_eq!(diversity[0], ClusterDiversity { job_categories: 1, platforms: 1, regions: 1, experience_levels: 1 });
+    assert_eq!(diversity[1], ClusterDiversity { job_categories: 2, platforms: 2, regions: 2, experience_levels: 2 });
+}
+
+/// Tests that abbreviated money formatting only shortens amounts of $1000 or more
+#[test]
+fn test_format_money() {
+    let abbreviated = ReportConfig { abbreviate_money: true };
+    assert_eq!(abbreviated.format_money(5000.0), "$5.0k");
+    assert_eq!(abbreviated.format_money(250.0), "$250.00");
+    assert_eq!(abbreviated.format_money(2_500_000.0), "$2.5M");
+    assert_eq!(abbreviated.format_money(-1500.0), "-$1.5k");
+
+    assert_eq!(ReportConfig::default().format_money(5000.0), "$5000.00");
+}
+
+/// Tests grouping by a composite (platform, experience level) key
+#[test]
+fn test_group_by_composite_key() {
+    let mut freelancers: Vec<Freelancer> = [(1000.0, 20.0), (3000.0, 40.0), (2000.0, 30.0), (5000.0, 60.0)]
+        .iter()
+        .enumerate()
+        .map(|(i, &(earnings, rate))| test_freelancer(i as u32, earnings, rate, 90.0))
+        .collect();
+    freelancers[2].experience_level = "Beginner".to_string();
+    freelancers[3].platform = "Fiverr".to_string();
+
+    let groups = group_by(&freelancers, |f| (f.platform.clone(), f.experience_level.clone()));
+    assert_eq!(groups.len(), 3);
+    let upwork_experts = groups[&("Upwork".to_string(), "Expert".to_string())];
+    assert_eq!(upwork_experts, GroupStats { count: 2, mean_rate: 30.0, mean_earnings: 2000.0 });
+    assert_eq!(groups[&("Upwork".to_string(), "Beginner".to_string())].count, 1);
+    assert_eq!(groups[&("Fiverr".to_string(), "Expert".to_string())].mean_rate, 60.0);
+}
+
+/// Tests that a region priced at the global mean gets a factor of 1.0 and adjusted rates line up
+#[test]
+fn test_region_adjustment_factors() {
+    let mut freelancers: Vec<Freelancer> = [20.0, 40.0, 30.0, 30.0]
+        .iter()
+        .enumerate()
+        .map(|(i, &rate)| test_freelancer(i as u32, 1000.0, rate, 90.0))
+        .collect();
+    freelancers[2].client_region = "Europe".to_string();
+    freelancers[3].client_region = "Europe".to_string();
+
+    let factors = region_adjustment_factors(&freelancers);
+    assert!((factors["Europe"] - 1.0).abs() < 1e-9);
+    assert!((factors["USA"] - 1.0).abs() < 1e-9);
+
+    freelancers[1].hourly_rate = 60.0;
+    let factors = region_adjustment_factors(&freelancers);
+    assert!((factors["Europe"] - 30.0 / 35.0).abs() < 1e-9);
+    let adjusted = region_adjusted_rates(&freelancers, &factors);
+    assert!((adjusted[2] - 35.0).abs() < 1e-9);
+    assert!((adjusted[0] - 20.0 / (40.0 / 35.0)).abs() < 1e-9);
+}
+
+/// Tests that cluster feature vectors have one row per cluster with the expected summaries
+#[test]
+fn test_cluster_feature_vectors() {
+    let mut freelancers: Vec<Freelancer> = [(1000.0, 20.0), (3000.0, 40.0), (2000.0, 30.0)]
+        .iter()
+        .enumerate()
+        .map(|(i, &(earnings, rate))| test_freelancer(i as u32, earnings, rate, 90.0))
+        .collect();
+    freelancers[2].job_category = "Design".to_string();
+    let clusters = vec![vec![0, 1], vec![2]];
+
+    let (features, names) = cluster_feature_vectors(&clusters, &freelancers);
+    assert_eq!(features.dim(), (clusters.len(), names.len()));
+    assert_eq!(names[1], "mean_hourly_rate");
+    assert_eq!(features.row(0).to_vec(), vec![2.0, 30.0, 2000.0, 90.0, 1.0]);
+    assert_eq!(features[[1, 4]], 0.0);
+}
+
+/// Tests that cluster performance averages match hand-computed values
+#[test]
+fn test_cluster_performance() {
+    let freelancers: Vec<Freelancer> = [(1000.0, 20.0), (3000.0, 45.0), (2000.0, 30.0)]
+        .iter()
+        .enumerate()
+        .map(|(i, &(earnings, rate))| test_freelancer(i as u32, earnings, rate, 90.0))
+        .collect();
+    let clusters = vec![vec![0, 1], vec![2, 99], vec![]];
+
+    let performance = cluster_performance(&clusters, &freelancers);
+    assert_eq!(performance, vec![
+        ClusterPerformance { cluster_id: 0, member_count: 2, avg_earnings: 2000.0, avg_hourly_rate: 32.5 },
+        ClusterPerformance { cluster_id: 1, member_count: 1, avg_earnings: 2000.0, avg_hourly_rate: 30.0 },
+        ClusterPerformance { cluster_id: 2, member_count: 0, avg_earnings: 0.0, avg_hourly_rate: 0.0 },
+    ]);
+}
+
+/// Tests that exported performance reads back with its header and rows, and that no clusters give a header only
+#[test]
+fn test_export_cluster_performance_csv() {
+    let path = std::env::temp_dir().join(format!("part1_cluster_performance_{}.csv", std::process::id()));
+    let path = path.to_string_lossy();
+    let performance = [ClusterPerformance { cluster_id: 0, member_count: 2, avg_earnings: 2000.0, avg_hourly_rate: 32.5 }];
+
+    export_cluster_performance_csv(&path, &performance).unwrap();
+    let mut reader = csv::Reader::from_path(&*path).unwrap();
+    assert_eq!(reader.headers().unwrap(), vec!["cluster_id", "member_count", "avg_earnings", "avg_hourly_rate"]);
+    let rows: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(&rows[0], vec!["0", "2", "2000", "32.5"]);
+
+    export_cluster_performance_csv(&path, &[]).unwrap();
+    let contents = std::fs::read_to_string(&*path).unwrap();
+    std::fs::remove_file(&*path).unwrap();
+    assert_eq!(contents, "cluster_id,member_count,avg_earnings,avg_hourly_rate\n");
+}
+
+/// Tests that the experience rate chart is written to the given path, including with empty clusters
+#[test]
+fn test_plot_cluster_experience_rates_path() {
+    let mut freelancers: Vec<Freelancer> = [20.0, 40.0, 30.0]
+        .iter()
+        .enumerate()
+        .map(|(i, &rate)| test_freelancer(i as u32, 1000.0, rate, 90.0))
+        .collect();
+    freelancers[1].experience_level = "Beginner".to_string();
+    let path = std::env::temp_dir().join(format!("part1_experience_rates_{}.png", std::process::id()));
+    let path = path.to_string_lossy();
+
+    for clusters in [vec![vec![0, 1], vec![2], vec![]], vec![]] {
+        plot_cluster_experience_rates(&clusters, &freelancers, &path).unwrap();
+        assert!(std::fs::metadata(&*path).unwrap().len() > 0);
+        std::fs::remove_file(&*path).unwrap();
+    }
+}
+
+/// Tests that a cluster whose rates are all zero still renders
+#[test]
+fn test_plot_cluster_experience_rates_all_zero() {
+    let mut freelancers: Vec<Freelancer> = (0..3).map(|i| test_freelancer(i, 0.0, 0.0, 90.0)).collect();
+    // An experience level the chart does not list contributes no bar at all
+    freelancers[2].experience_level = "Senior".to_string();
+    freelancers[2].hourly_rate = 80.0;
+    let path = std::env::temp_dir().join(format!("part1_zero_rates_{}.png", std::process::id()));
+    let path = path.to_string_lossy();
+
+    for clusters in [vec![vec![0, 1]], vec![vec![2]]] {
+        plot_cluster_experience_rates(&clusters, &freelancers, &path).unwrap();
+        assert!(std::fs::metadata(&*path).unwrap().len() > 0);
+        std::fs::remove_file(&*path).unwrap();
+    }
+}
+
+/// Tests that the rate histogram is written, including for equal rates, and rejects zero bins
+#[test]
+fn test_plot_hourly_rate_histogram() {
+    let freelancers: Vec<Freelancer> = [20.0, 35.0, 35.0, 80.0]
+        .iter()
+        .enumerate()
+        .map(|(i, &rate)| test_freelancer(i as u32, 1000.0, rate, 90.0))
+        .collect();
+    let equal: Vec<Freelancer> = (0..3).map(|i| test_freelancer(i, 1000.0, 40.0, 90.0)).collect();
+    let path = std::env::temp_dir().join(format!("part1_rate_histogram_{}.png", std::process::id()));
+    let path = path.to_string_lossy();
+
+    for data in [&freelancers, &equal] {
+        plot_hourly_rate_histogram(data, 5, &path).unwrap();
+        assert!(std::fs::metadata(&*path).unwrap().len() > 0);
+        std::fs::remove_file(&*path).unwrap();
+    }
+    assert!(plot_hourly_rate_histogram(&freelancers, 0, &path).is_err());
+}
+
+/// Tests interpolated percentiles and cluster quartiles against hand-computed values
+#[test]
+fn test_percentile_and_cluster_quartiles() {
+    let values = [8.0, 1.0, 6.0, 3.0, 5.0, 2.0, 7.0, 4.0];
+    // Ranks over 7 gaps: 25% -> 1.75, 50% -> 3.5, 75% -> 5.25
+    assert_eq!(percentile(&values, 25.0), Some(2.75));
+    assert_eq!(percentile(&values, 50.0), Some(4.5));
+    assert_eq!(percentile(&values, 75.0), Some(6.25));
+    assert_eq!(percentile(&values, 0.0), Some(1.0));
+    assert_eq!(percentile(&values, 100.0), Some(8.0));
+    assert_eq!(percentile(&[], 50.0), None);
+    assert_eq!(percentile(&values, 101.0), None);
+
+    let freelancers: Vec<Freelancer> = [(100.0, 10.0), (300.0, 20.0), (200.0, 40.0), (900.0, 30.0), (500.0, 99.0)]
+        .iter()
+        .enumerate()
+        .map(|(i, &(earnings, rate))| test_freelancer(i as u32, earnings, rate, 90.0))
+        .collect();
+    let quartiles = cluster_quartiles(&[0, 1, 2, 3], &freelancers).unwrap();
+    assert_eq!(quartiles.hourly_rate, Quartiles { q1: 17.5, median: 25.0, q3: 32.5 });
+    assert_eq!(quartiles.earnings, Quartiles { q1: 175.0, median: 250.0, q3: 450.0 });
+    assert_eq!(cluster_quartiles(&[], &freelancers), None);
+}
+
+/// Tests that top earners are sorted by earnings and capped at the cluster size
+#[test]
+fn test_top_earners() {
+    let freelancers: Vec<Freelancer> = [500.0, 2000.0, 100.0, 1500.0, 9000.0]
+        .iter()
+        .enumerate()
+        .map(|(i, &earnings)| test_freelancer(i as u32, earnings, 30.0, 90.0))
+        .collect();
+    let cluster = [0, 1, 2, 3];
+
+    assert_eq!(top_earners(&cluster, &freelancers, 2), vec![1, 3]);
+    assert_eq!(top_earners(&cluster, &freelancers, 10), vec![1, 3, 0, 2]);
+    assert!(top_earners(&cluster, &freelancers, 0).is_empty());
+}
@@ -0,0 +1,7 @@
+//! Library for the freelancer data analysis system.
+//!
+//! Exposes the loading, graph algorithm, and analysis modules used by the binary.
+
+pub mod data_loader;
+pub mod algorithms;
+pub mod analysis;
@@ -1,37 +1,53 @@
-/// Main module for the freelancer data analysis system.
+//! Main module for the freelancer data analysis system.
 
 use std::error::Error;
-use data_loader::load_freelancers;
-use algorithms::{build_collaboration_graph, find_connected_components};
-use analysis::{analyze_cluster_performance, analyze_cluster_profiles, plot_cluster_experience_rates};
-
-mod data_loader;
-mod algorithms;
-mod analysis;
+use part1::data_loader::{dedupe_by_id, load_freelancers_validated, RangePolicy};
+use part1::algorithms::{attribute_assortativity, best_threshold_by_modularity, build_collaboration_graph_checked, find_connected_components, DEFAULT_MAX_GRAPH_NODES};
+use part1::analysis::{analyze_cluster_performance, analyze_cluster_profiles, find_field_outliers, gini_coefficient, iqr_bounds, plot_cluster_experience_rates, plot_lorenz_curve};
 
 /// Main function that demonstrates the data analysis workflow.
-/// 1. Loads freelancer data from CSV file
+/// 1. Loads freelancer data from CSV file, clamping out-of-range values
 /// 2. Builds collaboration graph based on shared attributes
 /// 3. Finds connected components (clusters) in the graph
 /// 4. Analyzes cluster performance and profiles
-/// 5. Generates visualization of hourly rates by experience level
-
+/// 5. Generates visualizations of hourly rates by experience level and of earnings inequality
 fn main() -> Result<(), Box<dyn Error>> {
     // Load data
-    let freelancers = load_freelancers("freelancer_data.csv")?;
+    let mut freelancers = load_freelancers_validated("freelancer_data.csv", RangePolicy::Clamp)?;
+    let duplicates = dedupe_by_id(&mut freelancers);
+    if duplicates > 0 {
+        eprintln!("Removed {} duplicate freelancer rows", duplicates);
+    }
     
+    // Flag outliers in earnings and job success rate
+    let earnings: Vec<f32> = freelancers.iter().map(|f| f.earnings_usd).collect();
+    let earnings_outliers = find_field_outliers(&freelancers, |f| f.earnings_usd, iqr_bounds(&earnings));
+    let success_rates: Vec<f32> = freelancers.iter().map(|f| f.job_success_rate).collect();
+    let success_outliers = find_field_outliers(&freelancers, |f| f.job_success_rate, iqr_bounds(&success_rates));
+    println!("Earnings outliers: {}", earnings_outliers.len());
+    println!("Job success rate outliers: {}", success_outliers.len());
+    println!("Earnings Gini coefficient: {:.3}\n", gini_coefficient(&freelancers));
+
     // Build collaboration graph
-    let adj_list = build_collaboration_graph(&freelancers);
+    let adj_list = build_collaboration_graph_checked(&freelancers, DEFAULT_MAX_GRAPH_NODES, false)?;
     
     // Find connected components using BFS
     let clusters = find_connected_components(&adj_list);
+
+    // Report the threshold whose clusters have the highest modularity
+    let (best_threshold, best_q) = best_threshold_by_modularity(&freelancers, &[0.5, 0.6, 0.7, 0.8]);
+    println!("Suggested similarity threshold: {:.2} (modularity {:.4})", best_threshold, best_q);
+    let assortativity = attribute_assortativity(&adj_list, &freelancers, |f| f.experience_level.clone());
+    println!("Experience level assortativity: {:.3}\n", assortativity);
     
     // Print analysis
     analyze_cluster_performance(&clusters, &freelancers);
 
     analyze_cluster_profiles(&clusters, &freelancers);
 
-    plot_cluster_experience_rates(&clusters, &freelancers);
+    plot_cluster_experience_rates(&clusters, &freelancers, "cluster_experience_rates.png")?;
+
+    plot_lorenz_curve(&freelancers, "earnings_lorenz_curve.png")?;
 
     Ok(())
-}
\ No newline at end of file
+}
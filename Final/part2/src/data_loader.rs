@@ -1,11 +1,21 @@
-/// Module for loading and processing freelancer data from CSV files.
+//! Module for loading and processing freelancer data from CSV files.
 
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::Entry;
 use std::error::Error;
+use std::fmt;
 use std::fs::File;
-use csv::ReaderBuilder;
+use std::io::{self, BufReader, BufWriter, Read};
+use std::str::FromStr;
+use csv::{ReaderBuilder, StringRecord};
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
 
 /// Represents a freelancer with their professional attributes and performance metrics.
-/// 
+///
 /// # Fields
 /// `id` - Unique identifier for the freelancer
 /// `job_category` - Type of work the freelancer specializes in
@@ -15,6 +25,7 @@ use csv::ReaderBuilder;
 /// `earnings_usd` - Total earnings in USD
 /// `hourly_rate` - Charged hourly rate in USD
 /// `job_success_rate` - Percentage of successfully completed jobs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Freelancer {
     pub id: u32,
     pub job_category: String,
@@ -22,39 +33,891 @@ pub struct Freelancer {
     pub experience_level: String,
     pub client_region: String,
     pub earnings_usd: f32,
+    #[serde(with = "nan_as_null")]
     pub hourly_rate: f32,
     pub job_success_rate: f32,
 }
 
+/// Fluent builder for `Freelancer`, mainly for constructing test fixtures.
+///
+/// Unset fields take these defaults: id 1, "Web Development", "Upwork", "USA",
+/// "Intermediate", 1000.0 USD earnings, a 30.0 USD hourly rate and a 90.0% success rate,
+/// e.g. `FreelancerBuilder::new().job_category("Design").build()`.
+#[derive(Debug, Clone)]
+pub struct FreelancerBuilder {
+    freelancer: Freelancer,
+}
+
+impl Default for FreelancerBuilder {
+    fn default() -> Self {
+        FreelancerBuilder::new()
+    }
+}
+
+impl FreelancerBuilder {
+    /// Starts a builder with the documented defaults.
+    pub fn new() -> FreelancerBuilder {
+        FreelancerBuilder {
+            freelancer: Freelancer {
+                id: 1,
+                job_category: "Web Development".to_string(),
+                platform: "Upwork".to_string(),
+                client_region: "USA".to_string(),
+                experience_level: "Intermediate".to_string(),
+                earnings_usd: 1000.0,
+                hourly_rate: 30.0,
+                job_success_rate: 90.0,
+            },
+        }
+    }
+
+    /// Sets the freelancer id.
+    pub fn id(mut self, id: u32) -> Self {
+        self.freelancer.id = id;
+        self
+    }
+
+    /// Sets the job category.
+    pub fn job_category(mut self, job_category: impl Into<String>) -> Self {
+        self.freelancer.job_category = job_category.into();
+        self
+    }
+
+    /// Sets the platform.
+    pub fn platform(mut self, platform: impl Into<String>) -> Self {
+        self.freelancer.platform = platform.into();
+        self
+    }
+
+    /// Sets the client region.
+    pub fn client_region(mut self, client_region: impl Into<String>) -> Self {
+        self.freelancer.client_region = client_region.into();
+        self
+    }
+
+    /// Sets the experience level.
+    pub fn experience_level(mut self, experience_level: impl Into<String>) -> Self {
+        self.freelancer.experience_level = experience_level.into();
+        self
+    }
+
+    /// Sets the total earnings in USD.
+    pub fn earnings_usd(mut self, earnings_usd: f32) -> Self {
+        self.freelancer.earnings_usd = earnings_usd;
+        self
+    }
+
+    /// Sets the hourly rate in USD.
+    pub fn hourly_rate(mut self, hourly_rate: f32) -> Self {
+        self.freelancer.hourly_rate = hourly_rate;
+        self
+    }
+
+    /// Sets the job success rate as a percentage.
+    pub fn job_success_rate(mut self, job_success_rate: f32) -> Self {
+        self.freelancer.job_success_rate = job_success_rate;
+        self
+    }
+
+    /// Finishes the builder.
+    ///
+    /// # Returns: `Freelancer` - Freelancer with the defaults and every override applied
+    pub fn build(self) -> Freelancer {
+        self.freelancer
+    }
+}
+
+/// Experience level of a freelancer, shared by the analysis and the regression.
+///
+/// Data sets spell the lowest level either "Beginner" or "Entry Level"; both parse
+/// to `Entry`, so the same CSV works in both pipelines.
+/// part1 and part2 each keep a copy; `test_experience_level_spellings` pins the
+/// spellings so the two cannot drift apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ExperienceLevel {
+    Entry,
+    Intermediate,
+    Expert,
+}
+
+impl ExperienceLevel {
+    /// Every level, from least to most experienced.
+    pub const ALL: [ExperienceLevel; 3] = [ExperienceLevel::Entry, ExperienceLevel::Intermediate, ExperienceLevel::Expert];
+
+    /// Returns the display name of the level.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExperienceLevel::Entry => "Entry Level",
+            ExperienceLevel::Intermediate => "Intermediate",
+            ExperienceLevel::Expert => "Expert",
+        }
+    }
+
+    /// Returns the ordinal code of the level: 1 for entry, 2 for intermediate and 3 for expert.
+    pub fn ordinal(&self) -> u8 {
+        match self {
+            ExperienceLevel::Entry => 1,
+            ExperienceLevel::Intermediate => 2,
+            ExperienceLevel::Expert => 3,
+        }
+    }
+}
+
+impl FromStr for ExperienceLevel {
+    type Err = String;
+
+    /// Parses a level case-insensitively, accepting "Beginner", "Entry" and "Entry Level" for `Entry`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "beginner" | "entry" | "entry level" | "entry-level" => Ok(ExperienceLevel::Entry),
+            "intermediate" => Ok(ExperienceLevel::Intermediate),
+            "expert" => Ok(ExperienceLevel::Expert),
+            _ => Err(format!("unknown experience level '{}'", s)),
+        }
+    }
+}
+
+/// Errors raised while loading freelancer data.
+#[derive(Debug)]
+pub enum DataLoadError {
+    /// The file could not be opened or read.
+    Io(io::Error),
+    /// The CSV itself is malformed, e.g. a row has the wrong number of fields.
+    Csv(csv::Error),
+    /// A JSON file could not be written or parsed.
+    Json(serde_json::Error),
+    /// Required columns that are not in the header, in the order they were requested.
+    MissingColumns(Vec<String>),
+    /// A cell could not be converted to its field's type.
+    ///
+    /// `line` is the 1-based data row (the header is not counted), `column` the
+    /// 0-based column index, `field` the column's header name and `value` the raw cell.
+    ParseField { line: usize, column: usize, field: String, value: String },
+    /// A parsed value lies outside its field's valid range; see `validate_freelancers`.
+    ///
+    /// `line` is the 1-based data row and `field` the column's header name.
+    OutOfRange { line: usize, field: String, value: f32 },
+}
+
+impl fmt::Display for DataLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataLoadError::Io(e) => write!(f, "could not read freelancer data: {}", e),
+            DataLoadError::Csv(e) => write!(f, "malformed CSV: {}", e),
+            DataLoadError::Json(e) => write!(f, "invalid freelancer JSON: {}", e),
+            DataLoadError::MissingColumns(names) => {
+                let quoted: Vec<String> = names.iter().map(|name| format!("'{}'", name)).collect();
+                write!(f, "missing required column{} {}", if names.len() == 1 { "" } else { "s" }, quoted.join(", "))
+            }
+            DataLoadError::ParseField { line, column, field, value } => {
+                write!(f, "row {}: could not parse '{}' in column '{}' (index {})", line, value, field, column)
+            }
+            DataLoadError::OutOfRange { line, field, value } => {
+                write!(f, "row {}: {} in column '{}' is out of range", line, value, field)
+            }
+        }
+    }
+}
+
+impl Error for DataLoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DataLoadError::Io(e) => Some(e),
+            DataLoadError::Csv(e) => Some(e),
+            DataLoadError::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DataLoadError {
+    fn from(e: io::Error) -> Self {
+        DataLoadError::Io(e)
+    }
+}
+
+impl From<csv::Error> for DataLoadError {
+    fn from(e: csv::Error) -> Self {
+        DataLoadError::Csv(e)
+    }
+}
+
+impl From<serde_json::Error> for DataLoadError {
+    fn from(e: serde_json::Error) -> Self {
+        DataLoadError::Json(e)
+    }
+}
+
+/// Identifies one of the freelancer fields that can be read from the CSV file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    Id,
+    JobCategory,
+    Platform,
+    ExperienceLevel,
+    ClientRegion,
+    EarningsUsd,
+    HourlyRate,
+    JobSuccessRate,
+}
+
+impl FieldKind {
+    /// Every field, in CSV column order.
+    pub const ALL: [FieldKind; 8] = [
+        FieldKind::Id,
+        FieldKind::JobCategory,
+        FieldKind::Platform,
+        FieldKind::ExperienceLevel,
+        FieldKind::ClientRegion,
+        FieldKind::EarningsUsd,
+        FieldKind::HourlyRate,
+        FieldKind::JobSuccessRate,
+    ];
+
+    /// Categorical (string) fields, which are all the graph similarity needs.
+    pub const CATEGORICAL: [FieldKind; 4] = [
+        FieldKind::JobCategory,
+        FieldKind::Platform,
+        FieldKind::ExperienceLevel,
+        FieldKind::ClientRegion,
+    ];
+
+    /// Returns the CSV header name holding this field.
+    pub fn header(&self) -> &'static str {
+        match self {
+            FieldKind::Id => "Freelancer_ID",
+            FieldKind::JobCategory => "Job_Category",
+            FieldKind::Platform => "Platform",
+            FieldKind::ExperienceLevel => "Experience_Level",
+            FieldKind::ClientRegion => "Client_Region",
+            FieldKind::EarningsUsd => "Earnings_USD",
+            FieldKind::HourlyRate => "Hourly_Rate",
+            FieldKind::JobSuccessRate => "Job_Success_Rate",
+        }
+    }
+}
+
+/// Maps CSV header names to column indices.
+///
+/// Names are compared after trimming and lowercasing, so `Platform` and `platform`
+/// refer to the same column. When a name appears more than once, the first
+/// occurrence wins and the name is recorded in `duplicates`.
+pub struct HeaderMap {
+    indices: HashMap<String, usize>,
+    duplicates: Vec<String>,
+}
+
+impl HeaderMap {
+    /// Builds the name→index map from a header record.
+    ///
+    /// # Arguments: `headers` - Header row of the CSV file
+    ///
+    /// # Returns: `HeaderMap` - Map keeping the first index of every header name
+    pub fn from_headers(headers: &StringRecord) -> HeaderMap {
+        let mut indices = HashMap::new();
+        let mut duplicates = Vec::new();
+
+        for (index, name) in headers.iter().enumerate() {
+            let key = normalize_header(name);
+            match indices.entry(key) {
+                Entry::Occupied(entry) => {
+                    if !duplicates.contains(entry.key()) {
+                        duplicates.push(entry.key().clone());
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(index);
+                }
+            }
+        }
+        HeaderMap { indices, duplicates }
+    }
+
+    /// Looks up the column index for a header name.
+    ///
+    /// # Arguments: `name` - Header name, matched case-insensitively
+    ///
+    /// # Returns: `Result<usize, DataLoadError>` - Column index or `MissingColumns` if the column is missing
+    pub fn index(&self, name: &str) -> Result<usize, DataLoadError> {
+        self.indices
+            .get(&normalize_header(name))
+            .copied()
+            .ok_or_else(|| DataLoadError::MissingColumns(vec![name.to_string()]))
+    }
+
+    /// Returns the normalized names that appeared more than once in the header.
+    pub fn duplicates(&self) -> &[String] {
+        &self.duplicates
+    }
+
+    /// Builds the warning printed when the header contains duplicate names.
+    ///
+    /// # Returns: `Option<String>` - Warning listing the duplicates, or `None` if there are none
+    pub fn duplicate_warning(&self) -> Option<String> {
+        if self.duplicates.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "Warning: duplicate CSV columns [{}]; using the first occurrence of each",
+            self.duplicates.join(", ")
+        ))
+    }
+}
+
+/// Normalizes a header name for lookup.
+fn normalize_header(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
 /// Loads freelancer data from a CSV file.
-/// 
+///
 /// # Arguments: `path` - Path to the CSV file containing freelancer data
-/// 
-/// # Returns: `Result<Vec<Freelancer>, Box<dyn Error>>` - Vector of parsed freelancer data or error
-/// 
+///
+/// # Returns: `Result<Vec<Freelancer>, DataLoadError>` - Vector of parsed freelancer data or error
+///
+/// # Errors
+/// Returns `DataLoadError::Io` if the file cannot be read, `Csv` or `MissingColumns` if the CSV is malformed,
+/// and `ParseField` naming the row and column if a cell cannot be converted
+pub fn load_freelancers(path: &str) -> Result<Vec<Freelancer>, DataLoadError> {
+    let file = File::open(path)?;
+    read_freelancers(file)
+}
+
+/// Loads freelancer data from a gzip-compressed CSV file such as `freelancer_data.csv.gz`.
+///
+/// The decompressed CSV is parsed exactly like `load_freelancers`.
+///
+/// # Arguments: `path` - Path to the gzipped CSV file
+///
+/// # Returns: `Result<Vec<Freelancer>, DataLoadError>` - Vector of parsed freelancer data or error
+pub fn load_freelancers_gz(path: &str) -> Result<Vec<Freelancer>, DataLoadError> {
+    let file = File::open(path)?;
+    read_freelancers(GzDecoder::new(BufReader::new(file)))
+}
+
+/// Reads freelancer data from any CSV source.
+///
+/// Columns are located by header name, so their order in the file does not matter.
+/// A blank `Hourly_Rate` cell is read as NaN so the rate can be imputed later.
+///
+/// # Arguments: `reader` - Source of CSV data with a header row
+///
+/// # Returns: `Result<Vec<Freelancer>, DataLoadError>` - Vector of parsed freelancer data or error
+pub fn read_freelancers<R: Read>(reader: R) -> Result<Vec<Freelancer>, DataLoadError> {
+    read_freelancers_columns(reader, &FieldKind::ALL)
+}
+
+/// Removes freelancers whose id already appeared earlier, keeping the first occurrence.
+///
+/// # Arguments: `freelancers` - Loaded freelancers, deduplicated in place with their order preserved
+///
+/// # Returns: `usize` - Number of duplicate rows removed
+pub fn dedupe_by_id(freelancers: &mut Vec<Freelancer>) -> usize {
+    let before = freelancers.len();
+    let mut seen = HashSet::new();
+    freelancers.retain(|f| seen.insert(f.id));
+    before - freelancers.len()
+}
+
+/// How `validate_freelancers` treats values outside their valid range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangePolicy {
+    /// Fail with `DataLoadError::OutOfRange` on the first invalid value.
+    Strict,
+    /// Clamp invalid values to the nearest valid one.
+    Clamp,
+}
+
+/// Checks that success rates are percentages in [0, 100] and earnings and hourly rates are non-negative.
+///
+/// NaN values, such as missing hourly rates, are left untouched.
+///
+/// # Arguments: `freelancers` - Loaded freelancers in file order, `policy` - Whether to fail or clamp
+///
+/// # Returns: `Result<usize, DataLoadError>` - Number of values clamped; always 0 with `RangePolicy::Strict`
+///
 /// # Errors
-/// Returns error if file cannot be opened or read, CSV parsing fails, or data conversion fails
-pub fn load_freelancers(path: &str) -> Result<Vec<Freelancer>, Box<dyn Error>> {
+/// * Returns `DataLoadError::OutOfRange` naming the row and column of the first invalid value under `RangePolicy::Strict`
+pub fn validate_freelancers(freelancers: &mut [Freelancer], policy: RangePolicy) -> Result<usize, DataLoadError> {
+    let mut clamped = 0;
+    for (index, freelancer) in freelancers.iter_mut().enumerate() {
+        let checks = [
+            (FieldKind::EarningsUsd, &mut freelancer.earnings_usd, 0.0, f32::INFINITY),
+            (FieldKind::HourlyRate, &mut freelancer.hourly_rate, 0.0, f32::INFINITY),
+            (FieldKind::JobSuccessRate, &mut freelancer.job_success_rate, 0.0, 100.0),
+        ];
+        for (kind, value, min, max) in checks {
+            if value.is_nan() || (*value >= min && *value <= max) {
+                continue;
+            }
+            match policy {
+                RangePolicy::Strict => {
+                    return Err(DataLoadError::OutOfRange { line: index + 1, field: kind.header().to_string(), value: *value });
+                }
+                RangePolicy::Clamp => {
+                    *value = value.clamp(min, max);
+                    clamped += 1;
+                }
+            }
+        }
+    }
+    Ok(clamped)
+}
+
+/// Loads freelancer data from a CSV file and validates it with `validate_freelancers`.
+///
+/// # Arguments: `path` - Path to the CSV file, `policy` - Whether out-of-range values fail the load or are clamped
+///
+/// # Returns: `Result<Vec<Freelancer>, DataLoadError>` - Vector of validated freelancer data or error
+pub fn load_freelancers_validated(path: &str, policy: RangePolicy) -> Result<Vec<Freelancer>, DataLoadError> {
+    let mut freelancers = load_freelancers(path)?;
+    let clamped = validate_freelancers(&mut freelancers, policy)?;
+    if clamped > 0 {
+        eprintln!("Clamped {} out-of-range values in {}", clamped, path);
+    }
+    Ok(freelancers)
+}
+
+/// Loads only the requested fields of each freelancer from a CSV file.
+///
+/// Columns for fields that are not requested are neither required nor parsed.
+/// Unselected fields keep their defaults: empty strings, `0` for the id and
+/// `0.0` for every numeric field.
+///
+/// # Arguments: `path` - Path to the CSV file, `fields` - Fields to populate
+///
+/// # Returns: `Result<Vec<Freelancer>, DataLoadError>` - Vector of partially populated freelancers or error
+pub fn load_freelancers_columns(path: &str, fields: &[FieldKind]) -> Result<Vec<Freelancer>, DataLoadError> {
     let file = File::open(path)?;
-    let mut rdr = ReaderBuilder::new().has_headers(true).from_reader(file);
-    
+    read_freelancers_columns(file, fields)
+}
+
+/// Reads only the requested fields of each freelancer from any CSV source.
+///
+/// # Arguments: `reader` - Source of CSV data with a header row, `fields` - Fields to populate
+///
+/// # Returns: `Result<Vec<Freelancer>, DataLoadError>` - Vector of partially populated freelancers or error
+pub fn read_freelancers_columns<R: Read>(reader: R, fields: &[FieldKind]) -> Result<Vec<Freelancer>, DataLoadError> {
+    let mut rdr = ReaderBuilder::new().has_headers(true).from_reader(reader);
+
+    let headers = HeaderMap::from_headers(rdr.headers()?);
+    if let Some(warning) = headers.duplicate_warning() {
+        eprintln!("{}", warning);
+    }
+    let mut columns = Vec::new();
+    let mut missing = Vec::new();
+    for &kind in fields {
+        match headers.index(kind.header()) {
+            Ok(col) => columns.push((kind, col)),
+            Err(_) => missing.push(kind.header().to_string()),
+        }
+    }
+    if !missing.is_empty() {
+        return Err(DataLoadError::MissingColumns(missing));
+    }
+
     let mut freelancers = Vec::new();
-    for result in rdr.records() {
+    for (index, result) in rdr.records().enumerate() {
         let record = result?;
-        
-        let freelancer = Freelancer {
-            id: record[0].parse()?,
-            job_category: record[1].to_string(),
-            platform: record[2].to_string(),
-            experience_level: record[3].to_string(),
-            client_region: record[4].to_string(),
-            earnings_usd: record[7].parse()?,
-            hourly_rate: record[8].parse()?,
-            job_success_rate: record[9].parse()?,
-        };
-        
+        let mut freelancer = Freelancer::default();
+        for &(kind, col) in &columns {
+            let value = &record[col];
+            let parse_error = || DataLoadError::ParseField {
+                line: index + 1,
+                column: col,
+                field: kind.header().to_string(),
+                value: value.to_string(),
+            };
+            match kind {
+                FieldKind::Id => freelancer.id = parse_field(value, parse_error)?,
+                FieldKind::JobCategory => freelancer.job_category = value.to_string(),
+                FieldKind::Platform => freelancer.platform = value.to_string(),
+                FieldKind::ExperienceLevel => freelancer.experience_level = value.to_string(),
+                FieldKind::ClientRegion => freelancer.client_region = value.to_string(),
+                FieldKind::EarningsUsd => freelancer.earnings_usd = parse_field(value, parse_error)?,
+                FieldKind::HourlyRate => freelancer.hourly_rate = parse_optional_rate(value, parse_error)?,
+                FieldKind::JobSuccessRate => freelancer.job_success_rate = parse_field(value, parse_error)?,
+            }
+        }
         freelancers.push(freelancer);
     }
-    
     Ok(freelancers)
 }
+
+/// Parses an hourly rate, reading a blank cell as a missing (NaN) rate.
+fn parse_optional_rate(value: &str, error: impl FnOnce() -> DataLoadError) -> Result<f32, DataLoadError> {
+    if value.trim().is_empty() {
+        return Ok(f32::NAN);
+    }
+    parse_field(value, error)
+}
+
+/// Parses a numeric cell, reporting failures with `error`.
+fn parse_field<T: FromStr>(value: &str, error: impl FnOnce() -> DataLoadError) -> Result<T, DataLoadError> {
+    value.parse().map_err(|_| error())
+}
+
+/// Writes freelancers to a JSON file as an array of objects, one per freelancer. A missing (NaN) hourly
+/// rate is written as `null` and read back as NaN.
+///
+/// # Arguments: `path` - Output file, `freelancers` - Freelancers to save
+///
+/// # Returns: `Result<(), DataLoadError>` - Success or error writing the file
+pub fn save_freelancers_json(path: &str, freelancers: &[Freelancer]) -> Result<(), DataLoadError> {
+    let writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(writer, freelancers)?;
+    Ok(())
+}
+
+/// Loads freelancers from a JSON file written by `save_freelancers_json`.
+///
+/// # Arguments: `path` - Path to the JSON file
+///
+/// # Returns: `Result<Vec<Freelancer>, DataLoadError>` - Loaded freelancers or error
+pub fn load_freelancers_json(path: &str) -> Result<Vec<Freelancer>, DataLoadError> {
+    let reader = BufReader::new(File::open(path)?);
+    Ok(serde_json::from_reader(reader)?)
+}
+
+/// Serializes NaN floats as JSON `null`, which JSON has no number for, and reads `null` back as NaN.
+mod nan_as_null {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &f32, serializer: S) -> Result<S::Ok, S::Error> {
+        if value.is_nan() {
+            serializer.serialize_none()
+        } else {
+            serializer.serialize_some(value)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+        Ok(Option::<f32>::deserialize(deserializer)?.unwrap_or(f32::NAN))
+    }
+}
+
+/// Job categories and how often each appears in the sample data.
+const SYNTHETIC_CATEGORIES: [(&str, u32); 8] = [
+    ("Graphic Design", 265),
+    ("Web Development", 256),
+    ("App Development", 248),
+    ("Customer Support", 244),
+    ("Data Entry", 238),
+    ("SEO", 237),
+    ("Digital Marketing", 231),
+    ("Content Writing", 231),
+];
+
+/// Platforms and how often each appears in the sample data.
+const SYNTHETIC_PLATFORMS: [(&str, u32); 5] = [
+    ("Upwork", 420),
+    ("Toptal", 395),
+    ("Fiverr", 391),
+    ("Freelancer", 386),
+    ("PeoplePerHour", 358),
+];
+
+/// Client regions and how often each appears in the sample data.
+const SYNTHETIC_REGIONS: [(&str, u32); 7] = [
+    ("Australia", 298),
+    ("USA", 292),
+    ("Middle East", 291),
+    ("Asia", 281),
+    ("UK", 280),
+    ("Europe", 262),
+    ("Canada", 246),
+];
+
+/// Experience levels with their frequency and typical hourly rate in USD.
+const SYNTHETIC_EXPERIENCE: [(&str, u32, f32); 3] = [
+    ("Beginner", 668, 25.0),
+    ("Intermediate", 641, 45.0),
+    ("Expert", 641, 70.0),
+];
+
+/// Generates plausible random freelancers for tests and benchmarks.
+///
+/// Categories, platforms, regions and experience levels follow the frequencies of
+/// the bundled sample data. Hourly rates center on a per-experience base rate with
+/// ±15 USD of noise, so they rise with experience; earnings are the rate times 20
+/// to 150 worked hours. The same seed always produces the same data.
+///
+/// # Arguments: `n` - Number of freelancers, `seed` - Random seed
+///
+/// # Returns: `Vec<Freelancer>` - Freelancers with ids `1..=n`
+pub fn generate_synthetic_freelancers(n: usize, seed: u64) -> Vec<Freelancer> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (1..=n)
+        .map(|id| {
+            let job_category = pick_weighted(&mut rng, &SYNTHETIC_CATEGORIES);
+            let platform = pick_weighted(&mut rng, &SYNTHETIC_PLATFORMS);
+            let client_region = pick_weighted(&mut rng, &SYNTHETIC_REGIONS);
+            let &(experience_level, _, base_rate) = SYNTHETIC_EXPERIENCE
+                .choose_weighted(&mut rng, |level| level.1)
+                .unwrap_or(&SYNTHETIC_EXPERIENCE[0]);
+
+            let hourly_rate = (base_rate + rng.gen_range(-15.0..15.0f32)).max(5.0);
+            let hours = rng.gen_range(20.0..150.0f32);
+            Freelancer {
+                id: id as u32,
+                job_category: job_category.to_string(),
+                platform: platform.to_string(),
+                client_region: client_region.to_string(),
+                experience_level: experience_level.to_string(),
+                earnings_usd: (hourly_rate * hours).round(),
+                hourly_rate: (hourly_rate * 100.0).round() / 100.0,
+                job_success_rate: (rng.gen_range(50.0..100.0f32) * 100.0).round() / 100.0,
+            }
+        })
+        .collect()
+}
+
+/// Picks one value with probability proportional to its weight.
+fn pick_weighted(rng: &mut StdRng, choices: &[(&'static str, u32)]) -> &'static str {
+    choices.choose_weighted(rng, |choice| choice.1).map(|choice| choice.0).unwrap_or("")
+}
+
+/// Tests that duplicate header names are reported and the first column is used
+#[test]
+fn test_duplicate_headers_use_first_column() {
+    let csv = "Freelancer_ID,Job_Category,Platform,Experience_Level,Client_Region,Platform,Earnings_USD,Hourly_Rate,Job_Success_Rate\n\
+               1,Web Development,Fiverr,Expert,Asia,Upwork,1000,50.0,90.0\n";
+
+    let mut rdr = ReaderBuilder::new().has_headers(true).from_reader(csv.as_bytes());
+    let headers = HeaderMap::from_headers(rdr.headers().unwrap());
+    assert_eq!(headers.duplicates(), ["platform"]);
+    assert_eq!(headers.index("Platform").unwrap(), 2);
+    assert!(headers.duplicate_warning().unwrap().contains("platform"));
+
+    let freelancers = read_freelancers(csv.as_bytes()).unwrap();
+    assert_eq!(freelancers[0].platform, "Fiverr");
+}
+
+/// Tests that loading only categorical columns leaves numeric fields at their defaults
+#[test]
+fn test_read_categorical_columns_only() {
+    // The numeric columns hold text that would fail to parse if they were read
+    let csv = "Freelancer_ID,Job_Category,Platform,Experience_Level,Client_Region,Earnings_USD,Hourly_Rate,Job_Success_Rate\n\
+               1,Web Development,Fiverr,Expert,Asia,n/a,n/a,n/a\n";
+
+    let freelancers = read_freelancers_columns(csv.as_bytes(), &FieldKind::CATEGORICAL).unwrap();
+    assert_eq!(freelancers.len(), 1);
+    assert_eq!(freelancers[0].job_category, "Web Development");
+    assert_eq!(freelancers[0].platform, "Fiverr");
+    assert_eq!(freelancers[0].experience_level, "Expert");
+    assert_eq!(freelancers[0].client_region, "Asia");
+    assert_eq!(freelancers[0].id, 0);
+    assert_eq!(freelancers[0].earnings_usd, 0.0);
+    assert_eq!(freelancers[0].hourly_rate, 0.0);
+    assert_eq!(freelancers[0].job_success_rate, 0.0);
+}
+
+/// Tests that synthetic data is reproducible for a seed and has every field populated
+#[test]
+fn test_generate_synthetic_freelancers() {
+    let first = generate_synthetic_freelancers(200, 7);
+    let second = generate_synthetic_freelancers(200, 7);
+    assert_eq!(first, second);
+    assert_ne!(first, generate_synthetic_freelancers(200, 8));
+
+    assert_eq!(first.len(), 200);
+    for (i, f) in first.iter().enumerate() {
+        assert_eq!(f.id, i as u32 + 1);
+        assert!(!f.job_category.is_empty() && !f.platform.is_empty());
+        assert!(!f.client_region.is_empty() && !f.experience_level.is_empty());
+        assert!(f.hourly_rate >= 5.0 && f.earnings_usd > 0.0);
+        assert!((50.0..=100.0).contains(&f.job_success_rate));
+    }
+
+    // Rates rise with experience on average
+    let mean_rate = |level: &str| {
+        let rates: Vec<f32> = first.iter().filter(|f| f.experience_level == level).map(|f| f.hourly_rate).collect();
+        rates.iter().sum::<f32>() / rates.len() as f32
+    };
+    assert!(mean_rate("Beginner") < mean_rate("Intermediate"));
+    assert!(mean_rate("Intermediate") < mean_rate("Expert"));
+}
+
+/// Tests that a non-numeric hourly rate is reported as ParseField with its row and column
+#[test]
+fn test_parse_field_error() {
+    let csv = "Freelancer_ID,Job_Category,Platform,Experience_Level,Client_Region,Earnings_USD,Hourly_Rate,Job_Success_Rate\n\
+               1,Web Development,Fiverr,Expert,Asia,1000,50.0,90.0\n\
+               2,Design,Upwork,Beginner,USA,800,cheap,85.0\n";
+
+    match read_freelancers(csv.as_bytes()) {
+        Err(DataLoadError::ParseField { line, column, field, .. }) => {
+            assert_eq!(field, "Hourly_Rate");
+            assert_eq!(column, 6);
+            assert_eq!(line, 2);
+        }
+        other => panic!("expected a ParseField error, got {:?}", other),
+    }
+    assert!(matches!(load_freelancers("no_such_file.csv"), Err(DataLoadError::Io(_))));
+}
+
+/// Tests that a corrupt second row is reported with its row number and raw value
+#[test]
+fn test_parse_error_reports_row() {
+    let csv = "Freelancer_ID,Job_Category,Platform,Experience_Level,Client_Region,Earnings_USD,Hourly_Rate,Job_Success_Rate\n\
+               1,Web Development,Fiverr,Expert,Asia,1000,50.0,90.0\n\
+               2,Design,Upwork,Beginner,USA,12O0,40.0,85.0\n\
+               3,Writing,Upwork,Expert,UK,900,30.0,80.0\n";
+
+    let message = read_freelancers(csv.as_bytes()).unwrap_err().to_string();
+    assert!(message.contains("row 2"), "{}", message);
+    assert!(message.contains("'12O0'"), "{}", message);
+    assert!(message.contains("Earnings_USD"), "{}", message);
+}
+
+/// Tests that columns are found by name in any order and that every missing column is listed
+#[test]
+fn test_columns_by_header_name() {
+    let csv = "Hourly_Rate,Client_Region,Job_Success_Rate,Platform,Freelancer_ID,Earnings_USD,Experience_Level,Job_Category\n\
+               50.0,Asia,90.0,Fiverr,7,1000,Expert,Web Development\n";
+
+    let freelancers = read_freelancers(csv.as_bytes()).unwrap();
+    assert_eq!(freelancers[0].id, 7);
+    assert_eq!(freelancers[0].job_category, "Web Development");
+    assert_eq!(freelancers[0].platform, "Fiverr");
+    assert_eq!(freelancers[0].client_region, "Asia");
+    assert_eq!(freelancers[0].experience_level, "Expert");
+    assert_eq!(freelancers[0].earnings_usd, 1000.0);
+    assert_eq!(freelancers[0].hourly_rate, 50.0);
+    assert_eq!(freelancers[0].job_success_rate, 90.0);
+
+    let csv = "Freelancer_ID,Job_Category,Experience_Level,Client_Region,Earnings_USD,Job_Success_Rate\n";
+    let error = read_freelancers(csv.as_bytes()).unwrap_err();
+    assert!(matches!(&error, DataLoadError::MissingColumns(names) if names == &["Platform", "Hourly_Rate"]));
+    assert_eq!(error.to_string(), "missing required columns 'Platform', 'Hourly_Rate'");
+}
+
+/// Tests that freelancers survive a round trip through JSON unchanged
+#[test]
+fn test_json_round_trip() {
+    let mut freelancers = generate_synthetic_freelancers(5, 11);
+    freelancers[2].hourly_rate = f32::NAN;
+    let path = std::env::temp_dir().join(format!("part2_freelancers_{}.json", std::process::id()));
+    let path = path.to_string_lossy();
+
+    save_freelancers_json(&path, &freelancers).unwrap();
+    let mut loaded = load_freelancers_json(&path).unwrap();
+    std::fs::remove_file(&*path).unwrap();
+
+    // NaN never compares equal, so check it separately before comparing the rest
+    assert!(loaded[2].hourly_rate.is_nan());
+    freelancers[2].hourly_rate = 0.0;
+    loaded[2].hourly_rate = 0.0;
+    assert_eq!(loaded, freelancers);
+    assert!(matches!(load_freelancers_json("no_such_file.json"), Err(DataLoadError::Io(_))));
+}
+
+/// Tests that both spellings of the lowest experience level parse to the same variant
+#[test]
+fn test_experience_level_from_str() {
+    assert_eq!("Beginner".parse::<ExperienceLevel>(), Ok(ExperienceLevel::Entry));
+    assert_eq!("Entry Level".parse::<ExperienceLevel>(), Ok(ExperienceLevel::Entry));
+    assert_eq!(" expert ".parse::<ExperienceLevel>(), Ok(ExperienceLevel::Expert));
+    assert!("Senior".parse::<ExperienceLevel>().is_err());
+
+    let ordinals: Vec<u8> = ExperienceLevel::ALL.iter().map(ExperienceLevel::ordinal).collect();
+    assert_eq!(ordinals, vec![1, 2, 3]);
+}
+
+/// Tests the full spelling table, which must match the copy in the other crate
+#[test]
+fn test_experience_level_spellings() {
+    let table = [
+        ("Beginner", ExperienceLevel::Entry),
+        ("Entry", ExperienceLevel::Entry),
+        ("Entry Level", ExperienceLevel::Entry),
+        ("Entry-Level", ExperienceLevel::Entry),
+        ("Intermediate", ExperienceLevel::Intermediate),
+        ("Expert", ExperienceLevel::Expert),
+    ];
+    for (spelling, level) in table {
+        assert_eq!(spelling.parse::<ExperienceLevel>(), Ok(level), "{}", spelling);
+    }
+
+    let labels: Vec<(&str, u8)> = ExperienceLevel::ALL.iter().map(|level| (level.label(), level.ordinal())).collect();
+    assert_eq!(labels, vec![("Entry Level", 1), ("Intermediate", 2), ("Expert", 3)]);
+}
+
+/// Tests that a repeated id keeps only its first row
+#[test]
+fn test_dedupe_by_id() {
+    let csv = "Freelancer_ID,Job_Category,Platform,Experience_Level,Client_Region,Earnings_USD,Hourly_Rate,Job_Success_Rate\n\
+               1,Web Development,Fiverr,Expert,Asia,1000,50.0,90.0\n\
+               2,Design,Upwork,Beginner,USA,1200,40.0,85.0\n\
+               1,Writing,Upwork,Expert,UK,900,30.0,80.0\n";
+
+    let mut freelancers = read_freelancers(csv.as_bytes()).unwrap();
+    assert_eq!(dedupe_by_id(&mut freelancers), 1);
+    assert_eq!(freelancers.iter().map(|f| f.id).collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(freelancers[0].job_category, "Web Development");
+    assert_eq!(dedupe_by_id(&mut freelancers), 0);
+}
+
+/// Tests that a gzipped CSV loads the same records as the plain CSV
+#[test]
+fn test_load_freelancers_gz() {
+    use std::io::Write;
+
+    let csv = "Freelancer_ID,Job_Category,Platform,Experience_Level,Client_Region,Earnings_USD,Hourly_Rate,Job_Success_Rate\n\
+               1,Web Development,Fiverr,Expert,Asia,1000,50.0,90.0\n\
+               2,Design,Upwork,Beginner,USA,1200,40.0,85.0\n";
+    let path = std::env::temp_dir().join(format!("part2_freelancers_{}.csv.gz", std::process::id()));
+    let path = path.to_string_lossy();
+    let mut encoder = flate2::write::GzEncoder::new(File::create(&*path).unwrap(), flate2::Compression::default());
+    encoder.write_all(csv.as_bytes()).unwrap();
+    encoder.finish().unwrap();
+
+    let loaded = load_freelancers_gz(&path).unwrap();
+    std::fs::remove_file(&*path).unwrap();
+    let plain = read_freelancers(csv.as_bytes()).unwrap();
+    assert_eq!(loaded, plain);
+    assert!(matches!(load_freelancers_gz("no_such_file.csv.gz"), Err(DataLoadError::Io(_))));
+}
+
+/// Tests the builder's documented defaults and that overrides take effect
+#[test]
+fn test_freelancer_builder() {
+    let default = FreelancerBuilder::new().build();
+    assert_eq!(default.id, 1);
+    assert_eq!(default.job_category, "Web Development");
+    assert_eq!(default.platform, "Upwork");
+    assert_eq!(default.client_region, "USA");
+    assert_eq!(default.experience_level, "Intermediate");
+    assert_eq!(default.earnings_usd, 1000.0);
+    assert_eq!(default.hourly_rate, 30.0);
+    assert_eq!(default.job_success_rate, 90.0);
+
+    let designer = FreelancerBuilder::new().id(7).job_category("Design").hourly_rate(45.0).build();
+    assert_eq!(designer.id, 7);
+    assert_eq!(designer.job_category, "Design");
+    assert_eq!(designer.hourly_rate, 45.0);
+    assert_eq!(designer.platform, "Upwork");
+}
+
+/// Tests that a 150% success rate fails strict validation and is clamped to 100 otherwise
+#[test]
+fn test_validate_freelancers() {
+    let csv = "Freelancer_ID,Job_Category,Platform,Experience_Level,Client_Region,Earnings_USD,Hourly_Rate,Job_Success_Rate\n\
+               1,Web Development,Fiverr,Expert,Asia,1000,50.0,90.0\n\
+               2,Design,Upwork,Beginner,USA,1200,40.0,150.0\n\
+               3,Writing,Upwork,Expert,UK,-5,30.0,80.0\n";
+    let freelancers = read_freelancers(csv.as_bytes()).unwrap();
+
+    let error = validate_freelancers(&mut freelancers.clone(), RangePolicy::Strict).unwrap_err();
+    assert!(matches!(&error, DataLoadError::OutOfRange { line: 2, field, value } if field == "Job_Success_Rate" && *value == 150.0));
+    assert_eq!(error.to_string(), "row 2: 150 in column 'Job_Success_Rate' is out of range");
+
+    let mut clamped = freelancers.clone();
+    assert_eq!(validate_freelancers(&mut clamped, RangePolicy::Clamp).unwrap(), 2);
+    assert_eq!(clamped[1].job_success_rate, 100.0);
+    assert_eq!(clamped[2].earnings_usd, 0.0);
+    assert_eq!(clamped[0].job_success_rate, 90.0);
+
+    let mut valid = freelancers[..1].to_vec();
+    assert_eq!(validate_freelancers(&mut valid, RangePolicy::Strict).unwrap(), 0);
+}
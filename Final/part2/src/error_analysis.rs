@@ -1,25 +1,101 @@
-use ndarray::{Array1, Array2};
-use crate::data_loader::Freelancer;
+//! Module for evaluating regression predictions against actual hourly rates.
 
-pub fn analyze_errors(actual: &[f64], predicted: &[f64]) -> Result<(), Box<dyn std::error::Error>> {
-    // Calculate Mean Squared Error (MSE)
+use std::collections::HashMap;
+use plotters::prelude::*;
+use crate::data_loader::{Freelancer, load_freelancers};
+use crate::regression::{encode_features, RateModel};
+#[cfg(test)]
+use crate::data_loader::FreelancerBuilder;
+
+/// Summary error metrics for a set of predictions.
+/// 
+/// # Fields
+/// `mse` - Mean squared error
+/// `rmse` - Root mean squared error
+/// `mae` - Mean absolute error
+/// `r_squared` - Coefficient of determination
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorMetrics {
+    pub mse: f64,
+    pub rmse: f64,
+    pub mae: f64,
+    pub r_squared: f64,
+}
+
+/// Computes the summary error metrics for predicted against actual values.
+/// 
+/// # Arguments: `actual` - Actual hourly rates, `predicted` - Predicted hourly rates
+/// 
+/// # Returns: `ErrorMetrics` - MSE, RMSE, MAE and R-squared
+pub fn compute_error_metrics(actual: &[f64], predicted: &[f64]) -> ErrorMetrics {
     let mse = calculate_mse(actual, predicted);
+    ErrorMetrics {
+        mse,
+        rmse: mse.sqrt(),
+        mae: calculate_mae(actual, predicted),
+        r_squared: calculate_r_squared(actual, predicted),
+    }
+}
+
+/// Computes error metrics separately for each experience level.
+/// 
+/// Freelancers without a known (finite) hourly rate are skipped.
+/// 
+/// # Arguments
+/// `freelancers` - Freelancers whose actual hourly rates are compared
+/// `predicted` - Predicted hourly rate for each freelancer, in the same order
+/// 
+/// # Returns: `Result<HashMap<String, ErrorMetrics>, Box<dyn Error>>` - Metrics keyed by experience level
+/// 
+/// # Errors
+/// Returns error if there is not exactly one prediction per freelancer
+pub fn analyze_errors_by_experience(freelancers: &[Freelancer], predicted: &[f64]) -> Result<HashMap<String, ErrorMetrics>, Box<dyn std::error::Error>> {
+    if freelancers.len() != predicted.len() {
+        return Err(format!("{} freelancers but {} predictions", freelancers.len(), predicted.len()).into());
+    }
+    let mut groups: HashMap<String, (Vec<f64>, Vec<f64>)> = HashMap::new();
+    for (freelancer, &prediction) in freelancers.iter().zip(predicted.iter()) {
+        if !freelancer.hourly_rate.is_finite() {
+            continue;
+        }
+        let (actual, predicted) = groups.entry(freelancer.experience_level.clone()).or_default();
+        actual.push(freelancer.hourly_rate as f64);
+        predicted.push(prediction);
+    }
     
-    // Calculate Root Mean Squared Error (RMSE)
-    let rmse = mse.sqrt();
-    
-    // Calculate Mean Absolute Error (MAE)
-    let mae = calculate_mae(actual, predicted);
+    Ok(groups.into_iter()
+        .map(|(level, (actual, predicted))| (level, compute_error_metrics(&actual, &predicted)))
+        .collect())
+}
+
+/// Prints error metrics, sample predictions and residual diagnostics.
+/// 
+/// # Arguments: `actual` - Actual hourly rates, `predicted` - Predicted hourly rates,
+/// `n_features` - Number of model features, used for the adjusted R²
+/// 
+/// # Returns: `Result<ErrorMetrics, Box<dyn Error>>` - The printed MSE, RMSE, MAE and R-squared
+pub fn analyze_errors(actual: &[f64], predicted: &[f64], n_features: usize) -> Result<ErrorMetrics, Box<dyn std::error::Error>> {
+    // Calculate MSE, RMSE, MAE and R-squared
+    let metrics = compute_error_metrics(actual, predicted);
+    let mape = calculate_mape(actual, predicted);
+    let adjusted_r_squared = calculate_adjusted_r_squared(actual, predicted, n_features);
     
-    // Calculate R-squared
-    let r_squared = calculate_r_squared(actual, predicted);
+    // Calculate Durbin-Watson statistic on the residuals
+    let residuals: Vec<f64> = actual.iter()
+        .zip(predicted.iter())
+        .map(|(a, p)| a - p)
+        .collect();
+    let dw = durbin_watson(&residuals);
     
     // Print results
     println!("\nError Analysis:");
-    println!("Mean Squared Error (MSE): {:.2}", mse);
-    println!("Root Mean Squared Error (RMSE): {:.2}", rmse);
-    println!("Mean Absolute Error (MAE): {:.2}", mae);
-    println!("R-squared: {:.4}", r_squared);
+    println!("Mean Squared Error (MSE): {:.2}", metrics.mse);
+    println!("Root Mean Squared Error (RMSE): {:.2}", metrics.rmse);
+    println!("Mean Absolute Error (MAE): {:.2}", metrics.mae);
+    println!("Mean Absolute Percentage Error (MAPE): {:.2}%", mape);
+    println!("R-squared: {:.4}", metrics.r_squared);
+    println!("Adjusted R-squared: {:.4}", adjusted_r_squared);
+    println!("Durbin-Watson: {:.4}", dw);
     
     // Print some sample predictions vs actual
     println!("\nSample Predictions vs Actual:");
@@ -32,9 +108,168 @@ pub fn analyze_errors(actual: &[f64], predicted: &[f64]) -> Result<(), Box<dyn s
         );
     }
     
+    Ok(metrics)
+}
+
+/// Predicts every freelancer with the model and reports the errors against their actual rates.
+/// 
+/// Freelancers without a known (finite) hourly rate have nothing to compare against and are skipped.
+/// 
+/// # Arguments: `model` - Fitted rate model, `freelancers` - Freelancers to evaluate
+/// 
+/// # Returns: `Result<(Vec<f64>, Vec<f64>), Box<dyn Error>>` - Actual and predicted hourly rates of the evaluated freelancers
+pub fn evaluate_model(model: &RateModel, freelancers: &[Freelancer]) -> Result<(Vec<f64>, Vec<f64>), Box<dyn std::error::Error>> {
+    let known: Vec<&Freelancer> = freelancers.iter().filter(|f| f.hourly_rate.is_finite()).collect();
+    let actual: Vec<f64> = known.iter().map(|f| f.hourly_rate as f64).collect();
+    let predicted: Vec<f64> = known.iter()
+        .map(|f| model.predict(&encode_features(f)))
+        .collect();
+    analyze_errors(&actual, &predicted, model.coefficients.len())?;
+    Ok((actual, predicted))
+}
+
+/// Loads a held-out evaluation CSV and reports the model's out-of-sample errors.
+/// 
+/// # Arguments: `model` - Fitted rate model, `path` - Path to the evaluation CSV
+/// 
+/// # Returns: `Result<(Vec<f64>, Vec<f64>), Box<dyn Error>>` - Actual and predicted hourly rates
+pub fn evaluate_csv(model: &RateModel, path: &str) -> Result<(Vec<f64>, Vec<f64>), Box<dyn std::error::Error>> {
+    let freelancers = load_freelancers(path)?;
+    evaluate_model(model, &freelancers)
+}
+
+/// Finds the freelancers whose actual rate deviates most from the model's prediction.
+/// 
+/// Ranks by absolute residual, so both under- and over-priced freelancers surface.
+/// Freelancers without a known (finite) hourly rate have no residual and are skipped.
+/// 
+/// # Arguments: `model` - Fitted rate model, `freelancers` - Freelancers to rank, `n` - Number of results
+/// 
+/// # Returns: `Vec<(usize, f64)>` - Up to `n` freelancer indices with their signed residual (actual - predicted),
+/// largest absolute residual first
+pub fn top_mispriced(model: &RateModel, freelancers: &[Freelancer], n: usize) -> Vec<(usize, f64)> {
+    let mut residuals: Vec<(usize, f64)> = freelancers.iter()
+        .enumerate()
+        .filter(|(_, f)| f.hourly_rate.is_finite())
+        .map(|(i, f)| (i, f.hourly_rate as f64 - model.predict(&encode_features(f))))
+        .collect();
+    residuals.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+    residuals.truncate(n);
+    residuals
+}
+
+/// Plots predicted against actual hourly rates with a y = x reference line.
+/// 
+/// Both axes share one range covering every actual and predicted value, so points on
+/// the diagonal are perfect predictions and the distance from it is the error.
+/// 
+/// # Arguments: `actual` - Actual hourly rates (x), `predicted` - Predicted hourly rates (y), `path` - Output PNG file
+/// 
+/// # Returns: `Result<(), Box<dyn Error>>` - Success or error during plot generation
+/// 
+/// # Errors
+/// Returns error if `actual` and `predicted` have different lengths
+pub fn plot_predicted_vs_actual(actual: &[f64], predicted: &[f64], path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if actual.len() != predicted.len() {
+        return Err(format!("{} actual rates but {} predictions", actual.len(), predicted.len()).into());
+    }
+    let (low, high) = actual.iter()
+        .chain(predicted.iter())
+        .filter(|v| v.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+    let (low, high) = if low <= high { (low, high) } else { (0.0, 1.0) };
+    let padding = ((high - low) * 0.05).max(1.0);
+    let range = (low - padding)..(high + padding);
+    
+    let root = BitMapBackend::new(path, (800, 800)).into_drawing_area();
+    root.fill(&WHITE)?;
+    
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Predicted vs Actual Hourly Rate", ("sans-serif", 30))
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(range.clone(), range.clone())?;
+    
+    chart.configure_mesh()
+        .x_desc("Actual Hourly Rate (USD)")
+        .y_desc("Predicted Hourly Rate (USD)")
+        .bold_line_style(BLACK.mix(0.2))
+        .draw()?;
+    
+    chart.draw_series(LineSeries::new(vec![(range.start, range.start), (range.end, range.end)], RED))?
+        .label("y = x")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+    
+    chart.draw_series(
+        actual.iter()
+            .zip(predicted.iter())
+            .map(|(&a, &p)| Circle::new((a, p), 3, BLUE.mix(0.6).filled())),
+    )?;
+    
+    chart.configure_series_labels()
+        .position(SeriesLabelPosition::UpperLeft)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+    
+    root.present()?;
     Ok(())
 }
 
+/// Computes the root mean squared error with per-sample weights.
+/// 
+/// `sqrt(Σ w·(actual - predicted)² / Σ w)`; with equal weights this is the plain RMSE.
+/// 
+/// # Arguments: `actual` - Actual hourly rates, `predicted` - Predicted hourly rates, `weights` - Weight of each sample
+/// 
+/// # Returns: `Result<f64, Box<dyn Error>>` - Weighted RMSE, or NaN if the weights sum to zero
+/// 
+/// # Errors
+/// Returns error if the three slices differ in length
+pub fn weighted_rmse(actual: &[f64], predicted: &[f64], weights: &[f64]) -> Result<f64, Box<dyn std::error::Error>> {
+    if actual.len() != predicted.len() || actual.len() != weights.len() {
+        return Err(format!(
+            "{} actual rates, {} predictions and {} weights",
+            actual.len(), predicted.len(), weights.len()
+        ).into());
+    }
+    let total_weight: f64 = weights.iter().sum();
+    if total_weight <= 0.0 {
+        return Ok(f64::NAN);
+    }
+    let weighted_squares: f64 = actual.iter()
+        .zip(predicted.iter())
+        .zip(weights.iter())
+        .map(|((a, p), w)| w * (a - p).powi(2))
+        .sum();
+    Ok((weighted_squares / total_weight).sqrt())
+}
+
+/// Computes the RMSE weighted by each freelancer's earnings, so errors on high earners count more.
+/// 
+/// Freelancers without a known (finite) hourly rate are skipped.
+/// 
+/// # Arguments: `freelancers` - Freelancers whose actual rates are compared, `predicted` - Predicted rate per freelancer
+/// 
+/// # Returns: `Result<f64, Box<dyn Error>>` - Earnings-weighted RMSE, or NaN if total earnings are zero
+/// 
+/// # Errors
+/// Returns error if there is not exactly one prediction per freelancer
+pub fn earnings_weighted_rmse(freelancers: &[Freelancer], predicted: &[f64]) -> Result<f64, Box<dyn std::error::Error>> {
+    if freelancers.len() != predicted.len() {
+        return Err(format!("{} freelancers but {} predictions", freelancers.len(), predicted.len()).into());
+    }
+    let known: Vec<(&Freelancer, f64)> = freelancers.iter()
+        .zip(predicted.iter().copied())
+        .filter(|(f, _)| f.hourly_rate.is_finite())
+        .collect();
+    let actual: Vec<f64> = known.iter().map(|(f, _)| f.hourly_rate as f64).collect();
+    let predicted: Vec<f64> = known.iter().map(|&(_, p)| p).collect();
+    let weights: Vec<f64> = known.iter().map(|(f, _)| f.earnings_usd.max(0.0) as f64).collect();
+    weighted_rmse(&actual, &predicted, &weights)
+}
+
 fn calculate_mse(actual: &[f64], predicted: &[f64]) -> f64 {
     actual.iter()
         .zip(predicted.iter())
@@ -49,6 +284,10 @@ fn calculate_mae(actual: &[f64], predicted: &[f64]) -> f64 {
         .sum::<f64>() / actual.len() as f64
 }
 
+/// Computes R², which is undefined when every actual value is the same.
+/// 
+/// In that case there is no variance to explain, so a perfect prediction scores 1.0
+/// and anything else 0.0 instead of dividing by zero.
 fn calculate_r_squared(actual: &[f64], predicted: &[f64]) -> f64 {
     let mean_actual = actual.iter().sum::<f64>() / actual.len() as f64;
     
@@ -61,5 +300,291 @@ fn calculate_r_squared(actual: &[f64], predicted: &[f64]) -> f64 {
         .map(|(a, p)| (a - p).powi(2))
         .sum();
     
+    if total_sum_squares == 0.0 {
+        return if residual_sum_squares == 0.0 { 1.0 } else { 0.0 };
+    }
     1.0 - (residual_sum_squares / total_sum_squares)
-} 
\ No newline at end of file
+} 
+
+/// Computes the mean absolute percentage error, `mean(|actual - predicted| / |actual|) · 100`.
+/// 
+/// Rows whose actual rate is zero have no defined percentage error and would make
+/// the mean infinite, so they are skipped.
+/// 
+/// # Arguments: `actual` - Actual hourly rates, `predicted` - Predicted hourly rates
+/// 
+/// # Returns: `f64` - MAPE in percent, or NaN if every actual rate is zero
+pub fn calculate_mape(actual: &[f64], predicted: &[f64]) -> f64 {
+    let percentages: Vec<f64> = actual.iter()
+        .zip(predicted.iter())
+        .filter(|(a, _)| **a != 0.0)
+        .map(|(a, p)| ((a - p) / a).abs())
+        .collect();
+    percentages.iter().sum::<f64>() / percentages.len() as f64 * 100.0
+}
+
+/// Computes R² adjusted for the number of features.
+/// 
+/// `1 - (1 - R²)(n - 1) / (n - p - 1)` penalizes features that do not improve the fit
+/// enough to justify themselves, unlike plain R², which never decreases as features are added.
+/// 
+/// # Arguments: `actual` - Actual hourly rates, `predicted` - Predicted hourly rates, `n_features` - Number of features `p`
+/// 
+/// # Returns: `f64` - Adjusted R², or NaN when `n - p - 1 <= 0` leaves no residual degrees of freedom
+pub fn calculate_adjusted_r_squared(actual: &[f64], predicted: &[f64], n_features: usize) -> f64 {
+    let n = actual.len();
+    if n <= n_features + 1 {
+        return f64::NAN;
+    }
+    let r_squared = calculate_r_squared(actual, predicted);
+    1.0 - (1.0 - r_squared) * (n - 1) as f64 / (n - n_features - 1) as f64
+}
+
+/// Computes the Durbin-Watson statistic for residual autocorrelation.
+/// 
+/// Residuals are assumed to be in the order the freelancers appear in the data.
+/// A value near 2 means no autocorrelation, values toward 0 indicate positive
+/// autocorrelation and values toward 4 indicate negative autocorrelation.
+/// 
+/// # Arguments: `residuals` - Ordered residuals (actual - predicted)
+/// 
+/// # Returns: `f64` - Durbin-Watson statistic, or NaN if there are fewer than two residuals or all are zero
+pub fn durbin_watson(residuals: &[f64]) -> f64 {
+    let sum_squares: f64 = residuals.iter().map(|e| e.powi(2)).sum();
+    if residuals.len() < 2 || sum_squares == 0.0 {
+        return f64::NAN;
+    }
+    
+    let sum_diff_squares: f64 = residuals.windows(2)
+        .map(|w| (w[1] - w[0]).powi(2))
+        .sum();
+    
+    sum_diff_squares / sum_squares
+}
+
+/// Model predicting $10 plus $10 per experience level, ignoring the other features
+#[cfg(test)]
+fn create_experience_model() -> RateModel {
+    RateModel {
+        coefficients: ndarray::Array1::from_vec(vec![0.0, 0.0, 10.0]),
+        intercept: 10.0,
+        feature_names: vec!["Job Success Rate".to_string(), "Job Category".to_string(), "Experience Level".to_string()],
+    }
+}
+
+/// Tests that strongly positively autocorrelated residuals give a statistic well below 2
+#[test]
+fn test_durbin_watson_positive_autocorrelation() {
+    // Slowly drifting residuals: each one is close to the previous
+    let residuals = vec![1.0, 1.1, 1.2, 1.1, 1.0, -1.0, -1.1, -1.2, -1.1, -1.0];
+    let dw = durbin_watson(&residuals);
+    assert!(dw < 1.0, "expected strong positive autocorrelation, got {}", dw);
+
+    // Alternating residuals show negative autocorrelation instead
+    let alternating = vec![1.0, -1.0, 1.0, -1.0, 1.0, -1.0];
+    assert!(durbin_watson(&alternating) > 3.0);
+}
+
+/// Tests that errors are grouped and measured per experience level
+#[test]
+fn test_analyze_errors_by_experience() {
+    let make = |level: &str, rate: f32| Freelancer {
+        id: 0,
+        job_category: "Web Development".to_string(),
+        platform: "Upwork".to_string(),
+        experience_level: level.to_string(),
+        client_region: "USA".to_string(),
+        earnings_usd: 0.0,
+        hourly_rate: rate,
+        job_success_rate: 90.0,
+    };
+    let freelancers = vec![
+        make("Beginner", 10.0), make("Beginner", 20.0),
+        make("Expert", 50.0), make("Expert", 70.0),
+    ];
+    // Beginners are predicted perfectly, experts are each off by 10
+    let predicted = vec![10.0, 20.0, 60.0, 60.0];
+    
+    let metrics = analyze_errors_by_experience(&freelancers, &predicted).unwrap();
+    assert_eq!(metrics.len(), 2);
+    assert_eq!(metrics["Beginner"].mae, 0.0);
+    assert_eq!(metrics["Beginner"].r_squared, 1.0);
+    assert!((metrics["Expert"].mae - 10.0).abs() < 1e-9);
+    assert!((metrics["Expert"].rmse - 10.0).abs() < 1e-9);
+    assert!((metrics["Expert"].r_squared - 0.0).abs() < 1e-9);
+    
+    let error = analyze_errors_by_experience(&freelancers, &predicted[..3]).unwrap_err();
+    assert_eq!(error.to_string(), "4 freelancers but 3 predictions");
+}
+
+/// Tests evaluating a model against a small held-out CSV fixture
+#[test]
+fn test_evaluate_csv() {
+    let path = std::env::temp_dir().join(format!("part2_eval_fixture_{}.csv", std::process::id()));
+    std::fs::write(&path, "Freelancer_ID,Job_Category,Platform,Experience_Level,Client_Region,Earnings_USD,Hourly_Rate,Job_Success_Rate\n\
+                           1,Web Development,Upwork,Expert,USA,5000,50.0,90.0\n\
+                           2,Design,Fiverr,Entry Level,Europe,1000,20.0,80.0\n").unwrap();
+    let model = create_experience_model();
+    
+    let (actual, predicted) = evaluate_csv(&model, path.to_str().unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(actual, vec![50.0, 20.0]);
+    // Expert encodes as 3 and Entry Level as 1
+    assert_eq!(predicted, vec![40.0, 20.0]);
+}
+
+/// Tests that a dramatically underpriced freelancer ranks first with a negative residual
+#[test]
+fn test_top_mispriced() {
+    let model = create_experience_model();
+    // Experts are predicted at $40 and entry level freelancers at $20
+    let make = |level: &str, rate: f32| Freelancer {
+        experience_level: level.to_string(),
+        hourly_rate: rate,
+        ..Default::default()
+    };
+    let freelancers = vec![
+        make("Expert", 42.0), make("Entry Level", 5.0), make("Expert", 45.0), make("Entry Level", 21.0),
+    ];
+    
+    let ranked = top_mispriced(&model, &freelancers, 2);
+    assert_eq!(ranked, vec![(1, -15.0), (2, 5.0)]);
+    assert_eq!(top_mispriced(&model, &freelancers, 10).len(), 4);
+}
+
+/// Tests that the predicted vs actual chart is written as a PNG
+#[test]
+fn test_plot_predicted_vs_actual() {
+    let path = std::env::temp_dir().join(format!("part2_predicted_vs_actual_{}.png", std::process::id()));
+    let actual = [20.0, 35.0, 50.0, 80.0];
+    let predicted = [25.0, 30.0, 55.0, 70.0];
+    
+    assert!(plot_predicted_vs_actual(&actual, &predicted, path.to_str().unwrap()).is_ok());
+    let size = std::fs::metadata(&path).unwrap().len();
+    std::fs::remove_file(&path).unwrap();
+    assert!(size > 0);
+    
+    let error = plot_predicted_vs_actual(&actual, &predicted[..3], path.to_str().unwrap()).unwrap_err();
+    assert!(error.to_string().contains("4 actual rates but 3 predictions"));
+    assert!(!path.exists());
+}
+
+/// Tests that equal weights match the plain RMSE and that weighting one point shifts it
+#[test]
+fn test_weighted_rmse() {
+    let actual = [10.0, 20.0, 30.0, 40.0];
+    let predicted = [12.0, 18.0, 30.0, 30.0];
+    let plain = compute_error_metrics(&actual, &predicted).rmse;
+    
+    assert!((weighted_rmse(&actual, &predicted, &[1.0; 4]).unwrap() - plain).abs() < 1e-12);
+    assert!((weighted_rmse(&actual, &predicted, &[3.0; 4]).unwrap() - plain).abs() < 1e-12);
+    // The last point has the largest error, so favoring it raises the RMSE
+    assert!(weighted_rmse(&actual, &predicted, &[1.0, 1.0, 1.0, 10.0]).unwrap() > plain);
+    assert!(weighted_rmse(&actual, &predicted, &[0.0; 4]).unwrap().is_nan());
+    
+    let error = weighted_rmse(&actual, &predicted[..3], &[1.0; 4]).unwrap_err();
+    assert_eq!(error.to_string(), "4 actual rates, 3 predictions and 4 weights");
+    assert!(weighted_rmse(&actual, &predicted, &[1.0; 3]).is_err());
+    
+    let freelancers: Vec<Freelancer> = actual.iter()
+        .zip([1000.0, 1000.0, 1000.0, 10000.0])
+        .map(|(&rate, earnings)| Freelancer { hourly_rate: rate as f32, earnings_usd: earnings, ..Default::default() })
+        .collect();
+    let expected = weighted_rmse(&actual, &predicted, &[1.0, 1.0, 1.0, 10.0]).unwrap();
+    assert!((earnings_weighted_rmse(&freelancers, &predicted).unwrap() - expected).abs() < 1e-9);
+    assert!(earnings_weighted_rmse(&freelancers, &predicted[..3]).is_err());
+}
+
+/// Tests that R² stays finite when all actual rates are equal
+#[test]
+fn test_r_squared_constant_actuals() {
+    let actual = [30.0, 30.0, 30.0];
+    assert_eq!(compute_error_metrics(&actual, &[30.0, 30.0, 30.0]).r_squared, 1.0);
+    assert_eq!(compute_error_metrics(&actual, &[25.0, 30.0, 35.0]).r_squared, 0.0);
+    assert!((compute_error_metrics(&[10.0, 20.0, 30.0], &[10.0, 20.0, 40.0]).r_squared - 0.5).abs() < 1e-12);
+}
+
+/// Tests adjusted R² against a hand-computed value and its degrees-of-freedom guard
+#[test]
+fn test_adjusted_r_squared() {
+    // R² = 1 - 4 / 40 = 0.9 with n = 5, p = 2: 1 - 0.1 · 4 / 2 = 0.8
+    let actual = [10.0, 12.0, 14.0, 16.0, 18.0];
+    let predicted = [10.0, 12.0, 14.0, 16.0, 20.0];
+    assert!((calculate_adjusted_r_squared(&actual, &predicted, 2) - 0.8).abs() < 1e-12);
+    assert!(calculate_adjusted_r_squared(&actual, &predicted, 4).is_nan());
+    assert!(calculate_adjusted_r_squared(&[], &[], 0).is_nan());
+}
+
+/// Tests MAPE on known values and that zero actual rates are skipped
+#[test]
+fn test_calculate_mape() {
+    // Errors of 10% and 30%
+    assert!((calculate_mape(&[50.0, 20.0], &[55.0, 14.0]) - 20.0).abs() < 1e-9);
+    assert!((calculate_mape(&[50.0, 0.0, 20.0], &[55.0, 3.0, 14.0]) - 20.0).abs() < 1e-9);
+    assert!(calculate_mape(&[0.0], &[1.0]).is_nan());
+}
+
+/// Tests that analyze_errors returns the metrics it prints
+#[test]
+fn test_analyze_errors_returns_metrics() {
+    let actual = [10.0, 20.0, 30.0, 40.0];
+    let predicted = [12.0, 18.0, 33.0, 37.0];
+    let metrics = analyze_errors(&actual, &predicted, 1).unwrap();
+    
+    // Squared errors 4, 4, 9, 9 and total sum of squares 500
+    assert!((metrics.mse - 6.5).abs() < 1e-9);
+    assert!((metrics.rmse - metrics.mse.sqrt()).abs() < 1e-12);
+    assert!((metrics.mae - 2.5).abs() < 1e-9);
+    assert!((metrics.r_squared - (1.0 - 26.0 / 500.0)).abs() < 1e-9);
+}
+
+/// Tests that a freelancer with a blank rate is left out of the per-experience metrics
+#[test]
+fn test_analyze_errors_by_experience_skips_missing_rates() {
+    let freelancers: Vec<Freelancer> = [("Expert", 50.0), ("Expert", f32::NAN), ("Beginner", f32::NAN)].iter()
+        .map(|&(level, rate)| FreelancerBuilder::new().experience_level(level).hourly_rate(rate).build())
+        .collect();
+    
+    let metrics = analyze_errors_by_experience(&freelancers, &[40.0, 60.0, 20.0]).unwrap();
+    assert_eq!(metrics.len(), 1);
+    assert_eq!(metrics["Expert"].mae, 10.0);
+}
+
+/// Tests that in-sample evaluation skips a row whose rate cell is blank
+#[test]
+fn test_evaluate_csv_skips_missing_rates() {
+    let path = std::env::temp_dir().join(format!("part2_eval_blank_rate_{}.csv", std::process::id()));
+    std::fs::write(&path, "Freelancer_ID,Job_Category,Platform,Experience_Level,Client_Region,Earnings_USD,Hourly_Rate,Job_Success_Rate\n\
+                           1,Web Development,Upwork,Expert,USA,5000,50.0,90.0\n\
+                           2,Design,Fiverr,Expert,Europe,1000,,80.0\n\
+                           3,Design,Fiverr,Entry Level,Europe,1000,20.0,80.0\n").unwrap();
+    let model = create_experience_model();
+    
+    let (actual, predicted) = evaluate_csv(&model, path.to_str().unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(actual, vec![50.0, 20.0]);
+    assert_eq!(predicted, vec![40.0, 20.0]);
+}
+
+/// Tests that a freelancer with a blank rate is never ranked as mispriced
+#[test]
+fn test_top_mispriced_skips_missing_rates() {
+    let model = create_experience_model();
+    let freelancers: Vec<Freelancer> = [f32::NAN, 45.0, 30.0].iter()
+        .map(|&rate| FreelancerBuilder::new().experience_level("Expert").hourly_rate(rate).build())
+        .collect();
+    
+    assert_eq!(top_mispriced(&model, &freelancers, 10), vec![(2, -10.0), (1, 5.0)]);
+}
+
+/// Tests that a freelancer with a blank rate does not turn the earnings-weighted RMSE into NaN
+#[test]
+fn test_earnings_weighted_rmse_skips_missing_rates() {
+    let freelancers: Vec<Freelancer> = [(10.0, 1000.0), (f32::NAN, 5000.0), (20.0, 3000.0)].iter()
+        .map(|&(rate, earnings)| FreelancerBuilder::new().hourly_rate(rate).earnings_usd(earnings).build())
+        .collect();
+    
+    // Errors of 2 and 4 weighted 1:3 give sqrt((4 + 48) / 4)
+    let rmse = earnings_weighted_rmse(&freelancers, &[12.0, 99.0, 24.0]).unwrap();
+    assert!((rmse - 13.0f64.sqrt()).abs() < 1e-9);
+}
@@ -0,0 +1,9 @@
+//! Library for the freelancer hourly rate prediction system.
+//!
+//! Exposes the loading, regression, error analysis, prediction output, and run report modules used by the binary.
+
+pub mod data_loader;
+pub mod regression;
+pub mod error_analysis;
+pub mod predictions;
+pub mod report;
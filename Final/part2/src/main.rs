@@ -1,49 +1,122 @@
-/// Main module for the freelancer hourly rate prediction system.
+//! Main module for the freelancer hourly rate prediction system.
 
-mod data_loader;
-mod regression;
+use part2::data_loader::{dedupe_by_id, load_freelancers_validated, Freelancer, RangePolicy};
+use part2::regression::{compare_target_transforms, encode_features, explain_prediction_text, is_extrapolation, perform_regression, success_rate_elasticity, validate_query, FeatureSpec};
+use part2::error_analysis::{evaluate_csv, evaluate_model};
+use ndarray::Array2;
 
-use data_loader::{Freelancer, load_freelancers};
-use regression::perform_regression;
-use ndarray::array;
+/// Command line options for the prediction system.
+/// 
+/// # Fields
+/// `data_path` - Training CSV, the first positional argument (default "freelancer_data.csv")
+/// `eval_path` - Optional held-out CSV given with `--eval <path>`
+struct Options {
+    data_path: String,
+    eval_path: Option<String>,
+}
+
+/// Parses `[data.csv] [--eval <path>]` from the command line arguments.
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        data_path: "freelancer_data.csv".to_string(),
+        eval_path: None,
+    };
+    let mut args = args.skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--eval" {
+            options.eval_path = Some(args.next().ok_or("--eval requires a path")?);
+        } else {
+            options.data_path = arg;
+        }
+    }
+    Ok(options)
+}
 
 /// Main function that demonstrates the data loading, model training, and prediction demonstration.
-/// 1. Loads freelancer data from CSV
+/// 1. Loads freelancer data from CSV, clamping out-of-range values
 /// 2. Trains a linear regression model
 /// 3. Displays model parameters and example predictions
+/// 4. Reports prediction errors on `--eval <path>` if given, otherwise in-sample
+/// 5. Compares the raw and log-transformed rate targets on a held-out split
 /// 
 /// # Features Used
 /// - Job Success Rate (normalized to 0-1 range)
 /// - Job Category (encoded as 1-5)
 /// - Experience Level (encoded as 1-3)
-
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let options = parse_args(std::env::args())?;
+    
     // Load the freelancer data
-    let freelancers = load_freelancers("freelancer_data.csv")?;
+    let mut freelancers = load_freelancers_validated(&options.data_path, RangePolicy::Clamp)?;
+    let duplicates = dedupe_by_id(&mut freelancers);
+    if duplicates > 0 {
+        eprintln!("Removed {} duplicate freelancer rows", duplicates);
+    }
     
     // Perform regression analysis
-    let (coefficients, intercept) = perform_regression(&freelancers)?;
+    let model = perform_regression(&freelancers)?;
     
     // Print model results
     println!("Model Results:");
-    println!("Intercept: {:.2}", intercept);
+    println!("Intercept: {:.2}", model.intercept);
     println!("\nCoefficients:");
-    println!("Job Success Rate (0-1): {:.2}", coefficients[0]);
-    println!("Job Category (1-5): {:.2}", coefficients[1]);
-    println!("Experience Level (1-3): {:.2}", coefficients[2]);
+    println!("Job Success Rate (0-1): {:.2}", model.coefficients[0]);
+    println!("Job Category (1-5): {:.2}", model.coefficients[1]);
+    println!("Experience Level (1-3): {:.2}", model.coefficients[2]);
+    println!("\nSuccess rate elasticity: ${:.4}/hr per percentage point", success_rate_elasticity(&model));
     
     // Simple example predictions
     println!("\nExample Predictions:");
+    let spec = FeatureSpec::from_freelancers(&freelancers)?;
     
-    // Example 1: Expert Web Developer
-    let expert = array![[0.95, 1.0, 3.0]];  // 95% success, Web Dev, Expert
-    let pred_expert = intercept + expert.dot(&coefficients);
-    println!("Expert Web Developer: ${:.2}/hr", pred_expert);
+    let examples = [
+        ("Expert Web Developer", Freelancer {
+            job_category: "Web Development".to_string(),
+            experience_level: "Expert".to_string(),
+            job_success_rate: 95.0,
+            ..Default::default()
+        }),
+        ("Entry Level Designer", Freelancer {
+            job_category: "Design".to_string(),
+            experience_level: "Entry Level".to_string(),
+            job_success_rate: 75.0,
+            ..Default::default()
+        }),
+    ];
+    for (label, query) in &examples {
+        let features = encode_features(query);
+        let x = Array2::from_shape_vec((1, features.len()), features.clone())?;
+        if let Err(e) = validate_query(&spec, &x) {
+            println!("Warning: {}", e);
+        }
+        if is_extrapolation(&freelancers, query) {
+            println!("Warning: {} is outside the training data; the prediction is an extrapolation", label);
+        }
+        println!("{}: ${:.2}/hr", label, model.predict(&features));
+    }
+
+    // Explain the prediction for the first freelancer in the data
+    if let Some(first) = freelancers.first() {
+        println!("\nFreelancer {}: {}", first.id, explain_prediction_text(&model, first));
+    }
+
+    // Measure errors on held-out data when available
+    match &options.eval_path {
+        Some(path) => {
+            println!("\nEvaluating on {}", path);
+            evaluate_csv(&model, path)?;
+        }
+        None => {
+            println!("\nNo --eval file given; reporting in-sample errors");
+            evaluate_model(&model, &freelancers)?;
+        }
+    }
 
-    // Example 2: Entry Level Designer
-    let entry = array![[0.75, 3.0, 1.0]];  // 75% success, Design, Entry Level
-    let pred_entry = intercept + entry.dot(&coefficients);
-    println!("Entry Level Designer: ${:.2}/hr", pred_entry);
+    // Check whether fitting ln(rate) predicts better than the raw rate
+    println!("\nTarget Transform Comparison (test RMSE):");
+    for (name, rmse) in compare_target_transforms(&freelancers, 42)? {
+        println!("{}: {:.2}", name, rmse);
+    }
 
     Ok(())
-}
\ No newline at end of file
+}
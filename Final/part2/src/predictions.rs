@@ -0,0 +1,119 @@
+//! Module for writing per-freelancer predictions to CSV files.
+
+use std::error::Error;
+use std::fs::File;
+use csv::Writer;
+use crate::data_loader::Freelancer;
+
+/// Number of rows written between automatic flushes.
+const FLUSH_EVERY: usize = 1000;
+
+/// One freelancer's predicted hourly rate next to the actual one.
+/// 
+/// # Fields
+/// `id` - Freelancer identifier
+/// `actual_rate` - Observed hourly rate in USD
+/// `predicted_rate` - Model prediction in USD
+/// `residual` - `actual_rate - predicted_rate`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PredictionRow {
+    pub id: u32,
+    pub actual_rate: f64,
+    pub predicted_rate: f64,
+    pub residual: f64,
+}
+
+impl PredictionRow {
+    /// Builds the row for a freelancer and the rate predicted for them.
+    pub fn new(freelancer: &Freelancer, predicted_rate: f64) -> PredictionRow {
+        let actual_rate = freelancer.hourly_rate as f64;
+        PredictionRow {
+            id: freelancer.id,
+            actual_rate,
+            predicted_rate,
+            residual: actual_rate - predicted_rate,
+        }
+    }
+}
+
+/// Appends prediction rows to a CSV file as they are computed.
+/// 
+/// The header is written once when the file is created, and the buffer is flushed
+/// every `FLUSH_EVERY` rows, so large jobs never hold all rows in memory.
+pub struct PredictionWriter {
+    writer: Writer<File>,
+    rows_since_flush: usize,
+}
+
+impl PredictionWriter {
+    /// Creates (or truncates) the output file and writes the header row.
+    /// 
+    /// # Arguments: `path` - Output CSV path
+    /// 
+    /// # Returns: `Result<PredictionWriter, Box<dyn Error>>` - Writer ready for rows, or error if the file cannot be created
+    pub fn create(path: &str) -> Result<PredictionWriter, Box<dyn Error>> {
+        let mut writer = Writer::from_path(path)?;
+        writer.write_record(["Freelancer_ID", "Actual_Rate", "Predicted_Rate", "Residual"])?;
+        Ok(PredictionWriter { writer, rows_since_flush: 0 })
+    }
+
+    /// Appends one row, flushing if the flush interval has been reached.
+    /// 
+    /// # Arguments: `row` - Prediction to append
+    /// 
+    /// # Returns: `Result<(), Box<dyn Error>>` - Success or write error
+    pub fn write_row(&mut self, row: &PredictionRow) -> Result<(), Box<dyn Error>> {
+        self.writer.write_record(&[
+            row.id.to_string(),
+            row.actual_rate.to_string(),
+            row.predicted_rate.to_string(),
+            row.residual.to_string(),
+        ])?;
+        self.rows_since_flush += 1;
+        if self.rows_since_flush >= FLUSH_EVERY {
+            self.writer.flush()?;
+            self.rows_since_flush = 0;
+        }
+        Ok(())
+    }
+
+    /// Flushes any buffered rows and closes the file.
+    /// 
+    /// # Returns: `Result<(), Box<dyn Error>>` - Success or write error
+    pub fn finish(mut self) -> Result<(), Box<dyn Error>> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Tests that rows appended one at a time are read back unchanged
+#[test]
+fn test_prediction_writer_appends_rows() {
+    let path = std::env::temp_dir().join(format!("part2_predictions_{}.csv", std::process::id()));
+    let rows = vec![
+        PredictionRow { id: 1, actual_rate: 50.0, predicted_rate: 42.5, residual: 7.5 },
+        PredictionRow { id: 2, actual_rate: 20.0, predicted_rate: 25.25, residual: -5.25 },
+    ];
+    
+    let mut writer = PredictionWriter::create(path.to_str().unwrap()).unwrap();
+    for row in &rows {
+        writer.write_row(row).unwrap();
+    }
+    writer.finish().unwrap();
+    
+    let mut reader = csv::Reader::from_path(&path).unwrap();
+    assert_eq!(reader.headers().unwrap(), vec!["Freelancer_ID", "Actual_Rate", "Predicted_Rate", "Residual"]);
+    let read: Vec<PredictionRow> = reader.records()
+        .map(|record| {
+            let record = record.unwrap();
+            PredictionRow {
+                id: record[0].parse().unwrap(),
+                actual_rate: record[1].parse().unwrap(),
+                predicted_rate: record[2].parse().unwrap(),
+                residual: record[3].parse().unwrap(),
+            }
+        })
+        .collect();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(read, rows);
+}
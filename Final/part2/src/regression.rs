@@ -1,117 +1,1865 @@
-/// Module for performing linear regression analysis on freelancer data.
-/// Implements a simple linear regression model to predict hourly rates based on various features.
+//! Module for performing linear regression analysis on freelancer data.
+//! Implements a simple linear regression model to predict hourly rates based on various features.
 
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
 use linfa::Dataset;
 use linfa::traits::Fit;
-use ndarray::{Array1, Array2, array};
+use ndarray::{Array1, Array2, Axis};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
 use linfa_linear::LinearRegression;
-use crate::data_loader::Freelancer;
+use serde::{Deserialize, Serialize};
+use crate::data_loader::{ExperienceLevel, Freelancer};
+#[cfg(test)]
+use crate::data_loader::FreelancerBuilder;
+use crate::error_analysis::compute_error_metrics;
 
-/// Performs linear regression on freelancer data to predict hourly rates.
+/// Names of the regression features, in the order they appear in a feature vector.
+pub const FEATURE_NAMES: [&str; 3] = ["Job Success Rate", "Job Category", "Experience Level"];
+
+/// Errors raised by the regression before any fitting is attempted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegressionError {
+    /// No more usable rows than features, so the coefficients and intercept cannot be identified.
+    InsufficientData { rows: usize, features: usize },
+}
+
+impl std::fmt::Display for RegressionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegressionError::InsufficientData { rows, features } => write!(
+                f,
+                "insufficient data: {} usable rows for {} features; need at least {}",
+                rows, features, features + 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RegressionError {}
+
+/// A fitted linear model predicting hourly rates from encoded features.
 /// 
-/// # Arguments: `freelancers` - Slice of Freelancer structs containing the training data
+/// # Fields
+/// `coefficients` - Weight for each feature, aligned with `feature_names`
+/// `intercept` - Constant term of the model
+/// `feature_names` - Human readable name of each feature
+pub struct RateModel {
+    pub coefficients: Array1<f64>,
+    pub intercept: f64,
+    pub feature_names: Vec<String>,
+}
+
+impl RateModel {
+    /// Predicts the hourly rate for one encoded feature vector.
+    /// 
+    /// # Arguments: `features` - Encoded features in the same order as `feature_names`
+    /// 
+    /// # Returns: `f64` - Predicted hourly rate in USD
+    pub fn predict(&self, features: &[f64]) -> f64 {
+        self.intercept + self.coefficients.iter()
+            .zip(features.iter())
+            .map(|(c, x)| c * x)
+            .sum::<f64>()
+    }
+    
+    /// Predicts the hourly rate for every row of a feature matrix.
+    /// 
+    /// # Arguments: `x` - One encoded feature vector per row, columns in the order of `feature_names`
+    /// 
+    /// # Returns: `Array1<f64>` - Predicted hourly rate per row
+    /// 
+    /// # Panics
+    /// Panics if `x` does not have one column per coefficient
+    pub fn predict_batch(&self, x: &Array2<f64>) -> Array1<f64> {
+        x.dot(&self.coefficients) + self.intercept
+    }
+    
+    /// Saves the coefficients, intercept and feature names as JSON.
+    /// 
+    /// # Arguments: `path` - Output file
+    /// 
+    /// # Returns: `Result<(), Box<dyn Error>>` - Success or error writing the file
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let saved = SavedRateModel {
+            coefficients: self.coefficients.to_vec(),
+            intercept: self.intercept,
+            feature_names: self.feature_names.clone(),
+        };
+        serde_json::to_writer_pretty(BufWriter::new(File::create(path)?), &saved)?;
+        Ok(())
+    }
+    
+    /// Loads a model written by `save`.
+    /// 
+    /// # Arguments: `path` - Path to the JSON file
+    /// 
+    /// # Returns: `Result<RateModel, Box<dyn Error>>` - Model predicting exactly like the saved one
+    /// 
+    /// # Errors
+    /// Returns error if the file cannot be read or parsed, or if the number of
+    /// coefficients does not match the number of feature names
+    pub fn load(path: &str) -> Result<RateModel, Box<dyn std::error::Error>> {
+        let saved: SavedRateModel = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+        if saved.coefficients.len() != saved.feature_names.len() {
+            return Err(format!(
+                "saved model has {} coefficients for {} features",
+                saved.coefficients.len(),
+                saved.feature_names.len()
+            ).into());
+        }
+        Ok(RateModel {
+            coefficients: Array1::from_vec(saved.coefficients),
+            intercept: saved.intercept,
+            feature_names: saved.feature_names,
+        })
+    }
+}
+
+/// On-disk form of a `RateModel`.
+#[derive(Serialize, Deserialize)]
+struct SavedRateModel {
+    coefficients: Vec<f64>,
+    intercept: f64,
+    feature_names: Vec<String>,
+}
+
+/// Encodes a freelancer into the numeric features used by the regression.
+/// 
+/// # Arguments: `freelancer` - Freelancer to encode
 /// 
-/// # Returns: `Result<(Array1<f64>, f64), Box<dyn Error>>` - Tuple containing:
-///   - Coefficients for each feature
-///   - Intercept term
+/// # Returns: `Vec<f64>` - Features in the order of `FEATURE_NAMES`
 /// 
 /// # Features Used
 /// 1. Job Success Rate (normalized to 0-1 range)
 /// 2. Job Category (encoded as 1-5)
-/// 3. Experience Level (encoded as 1-3)
-pub fn perform_regression(freelancers: &[Freelancer]) -> Result<(Array1<f64>, f64), Box<dyn std::error::Error>> {
+/// 3. Experience Level (`ExperienceLevel::ordinal`, 1-3; "Beginner" counts as entry level)
+/// 
+/// Unknown categories and experience levels are encoded as 0.0.
+pub fn encode_features(freelancer: &Freelancer) -> Vec<f64> {
+    // Convert job success rate from percentage (50-100) to 0-1 range
+    let normalized_success = (freelancer.job_success_rate as f64) / 100.0;
+    
+    // Convert categorical variables to numerical values
+    let job_category_value = match freelancer.job_category.as_str() {
+        "Web Development" => 1.0,
+        "Mobile Development" => 2.0,
+        "Design" => 3.0,
+        "Writing" => 4.0,
+        "Data Science" => 5.0,
+        _ => 0.0,
+    };
+    
+    let experience_value = freelancer.experience_level
+        .parse::<ExperienceLevel>()
+        .map_or(0.0, |level| level.ordinal() as f64);
+    
+    vec![
+        normalized_success,
+        job_category_value,
+        experience_value,
+    ]
+}
+
+/// One-hot encoding of job category and experience level, learned from training data.
+/// 
+/// Each distinct category and experience level becomes its own 0/1 feature, so the
+/// model no longer treats the ordinal codes of `encode_features` as magnitudes.
+/// Platform and client region are only encoded by `from_freelancers_with_location`;
+/// otherwise their lists are empty and add no features.
+/// 
+/// # Fields
+/// `job_categories` - Distinct job categories in alphabetical order
+/// `experience_levels` - Distinct experience levels in alphabetical order
+/// `platforms` - Distinct platforms in alphabetical order, or empty
+/// `client_regions` - Distinct client regions in alphabetical order, or empty
+#[derive(Debug, Clone, PartialEq)]
+pub struct OneHotEncoder {
+    pub job_categories: Vec<String>,
+    pub experience_levels: Vec<String>,
+    pub platforms: Vec<String>,
+    pub client_regions: Vec<String>,
+}
+
+/// Sorted distinct values of one attribute of `freelancers`.
+fn distinct_values(freelancers: &[Freelancer], field: fn(&Freelancer) -> &String) -> Vec<String> {
+    let mut values: Vec<String> = freelancers.iter().map(|f| field(f).clone()).collect();
+    values.sort_unstable();
+    values.dedup();
+    values
+}
+
+/// 1.0 if `value` equals `level`, otherwise 0.0.
+fn indicator(level: &str, value: &str) -> f64 {
+    if level == value { 1.0 } else { 0.0 }
+}
+
+impl OneHotEncoder {
+    /// Collects the distinct job categories and experience levels of `freelancers`.
+    pub fn from_freelancers(freelancers: &[Freelancer]) -> OneHotEncoder {
+        OneHotEncoder {
+            job_categories: distinct_values(freelancers, |f| &f.job_category),
+            experience_levels: distinct_values(freelancers, |f| &f.experience_level),
+            platforms: Vec::new(),
+            client_regions: Vec::new(),
+        }
+    }
+    
+    /// Collects the distinct job categories, experience levels, platforms and client regions.
+    pub fn from_freelancers_with_location(freelancers: &[Freelancer]) -> OneHotEncoder {
+        OneHotEncoder {
+            platforms: distinct_values(freelancers, |f| &f.platform),
+            client_regions: distinct_values(freelancers, |f| &f.client_region),
+            ..OneHotEncoder::from_freelancers(freelancers)
+        }
+    }
+    
+    /// Names of the encoded features, e.g. "Job Category = Design".
+    pub fn feature_names(&self) -> Vec<String> {
+        std::iter::once(FEATURE_NAMES[0].to_string())
+            .chain(self.job_categories.iter().map(|c| format!("{} = {}", FEATURE_NAMES[1], c)))
+            .chain(self.experience_levels.iter().map(|e| format!("{} = {}", FEATURE_NAMES[2], e)))
+            .chain(self.platforms.iter().map(|p| format!("Platform = {}", p)))
+            .chain(self.client_regions.iter().map(|r| format!("Client Region = {}", r)))
+            .collect()
+    }
+    
+    /// Encodes a freelancer as success rate (0-1) followed by the category, experience,
+    /// platform and client region indicators.
+    /// 
+    /// A value not seen in training has no indicator, so its block is all zeros.
+    pub fn encode(&self, freelancer: &Freelancer) -> Vec<f64> {
+        let mut features = vec![freelancer.job_success_rate as f64 / 100.0];
+        features.extend(self.job_categories.iter().map(|c| indicator(c, &freelancer.job_category)));
+        features.extend(self.experience_levels.iter().map(|e| indicator(e, &freelancer.experience_level)));
+        features.extend(self.platforms.iter().map(|p| indicator(p, &freelancer.platform)));
+        features.extend(self.client_regions.iter().map(|r| indicator(r, &freelancer.client_region)));
+        features
+    }
+    
+    /// Encodes every freelancer into one row of a feature matrix.
+    /// 
+    /// # Returns: `Result<Array2<f64>, Box<dyn Error>>` - One row per freelancer, one column per `feature_names` entry
+    pub fn build_matrix(&self, freelancers: &[Freelancer]) -> Result<Array2<f64>, Box<dyn std::error::Error>> {
+        let rows: Vec<f64> = freelancers.iter().flat_map(|f| self.encode(f)).collect();
+        Ok(Array2::from_shape_vec((freelancers.len(), self.width()), rows)?)
+    }
+    
+    /// Number of encoded features.
+    fn width(&self) -> usize {
+        1 + self.job_categories.len() + self.experience_levels.len() + self.platforms.len() + self.client_regions.len()
+    }
+    
+    /// Column of the first (reference) level of every non-empty indicator block.
+    fn reference_columns(&self) -> Vec<usize> {
+        let mut references = Vec::new();
+        let mut start = 1;
+        for block in [&self.job_categories, &self.experience_levels, &self.platforms, &self.client_regions] {
+            if !block.is_empty() {
+                references.push(start);
+            }
+            start += block.len();
+        }
+        references
+    }
+}
+
+/// Performs linear regression with one-hot encoded job category and experience level.
+/// 
+/// A full set of indicators plus the intercept is collinear, so the first (alphabetical)
+/// level of each attribute is the reference: it is left out of the fit, gets a coefficient
+/// of 0.0 and the other coefficients are differences from it. Unknown categories encode as
+/// all zeros and are therefore predicted like the reference level. Freelancers without a
+/// known rate are skipped.
+/// 
+/// # Arguments: `freelancers` - Slice of Freelancer structs containing the training data
+/// 
+/// # Returns: `Result<(RateModel, OneHotEncoder), Box<dyn Error>>` - Model whose `feature_names`
+/// label every coefficient, and the encoder for new queries
+pub fn perform_one_hot_regression(freelancers: &[Freelancer]) -> Result<(RateModel, OneHotEncoder), Box<dyn std::error::Error>> {
+    fit_one_hot_regression(freelancers, OneHotEncoder::from_freelancers)
+}
+
+/// Performs one-hot regression that also encodes platform and client region.
+/// 
+/// Works like `perform_one_hot_regression`, with the first (alphabetical) platform and
+/// client region as additional reference levels. Unknown platforms and regions are
+/// predicted like the reference level.
+/// 
+/// # Arguments: `freelancers` - Slice of Freelancer structs containing the training data
+/// 
+/// # Returns: `Result<(RateModel, OneHotEncoder), Box<dyn Error>>` - Model whose `feature_names`
+/// label every coefficient, and the encoder for new queries
+pub fn perform_location_regression(freelancers: &[Freelancer]) -> Result<(RateModel, OneHotEncoder), Box<dyn std::error::Error>> {
+    fit_one_hot_regression(freelancers, OneHotEncoder::from_freelancers_with_location)
+}
+
+/// Fits a one-hot regression with the encoder learned by `learn_encoder`, dropping each block's reference level.
+fn fit_one_hot_regression(
+    freelancers: &[Freelancer],
+    learn_encoder: fn(&[Freelancer]) -> OneHotEncoder,
+) -> Result<(RateModel, OneHotEncoder), Box<dyn std::error::Error>> {
+    let training: Vec<Freelancer> = freelancers.iter().filter(|f| !f.hourly_rate.is_nan()).cloned().collect();
+    if training.is_empty() {
+        return Err("cannot fit a regression without data".into());
+    }
+    let encoder = learn_encoder(&training);
+    let x = encoder.build_matrix(&training)?;
+    let y: Array1<f64> = training.iter().map(|f| f.hourly_rate as f64).collect();
+    
+    let references = encoder.reference_columns();
+    let kept: Vec<usize> = (0..x.ncols())
+        .filter(|column| !references.contains(column))
+        .collect();
+    let x_kept = x.select(Axis(1), &kept);
+    check_collinearity(&x_kept)?;
+    
+    let (fitted, intercept) = fit_weighted_least_squares(&x_kept, &y, &Array1::ones(y.len()))?;
+    let mut coefficients = Array1::zeros(x.ncols());
+    for (&column, &coefficient) in kept.iter().zip(fitted.iter()) {
+        coefficients[column] = coefficient;
+    }
+    let model = RateModel { coefficients, intercept, feature_names: encoder.feature_names() };
+    Ok((model, encoder))
+}
+
+/// Performs linear regression on freelancer data to predict hourly rates.
+/// 
+/// Constant features are reported with a warning and left out of the fit; see
+/// `perform_regression_with`.
+/// 
+/// # Arguments: `freelancers` - Slice of Freelancer structs containing the training data
+/// 
+/// # Returns: `Result<RateModel, Box<dyn Error>>` - Fitted model with one coefficient per feature
+/// 
+/// # Errors
+/// Returns `RegressionError::InsufficientData` unless there are more usable rows than features, or an error if the
+/// features are collinear (see `check_collinearity`) or the fit fails
+/// 
+/// # Features Used
+/// See `encode_features`.
+pub fn perform_regression(freelancers: &[Freelancer]) -> Result<RateModel, Box<dyn std::error::Error>> {
+    perform_regression_with(freelancers, true)
+}
+
+/// Performs linear regression, choosing how constant features are handled.
+/// 
+/// Constant (zero-variance) features are always reported on stderr. When
+/// `drop_constant_features` is set they are left out of the fit and get a
+/// coefficient of 0.0, so the model still has one coefficient per feature.
+/// Otherwise they stay in and the fit fails the collinearity check.
+/// 
+/// # Arguments: `freelancers` - Training data, `drop_constant_features` - Drop constant columns before fitting
+/// 
+/// # Returns: `Result<RateModel, Box<dyn Error>>` - Fitted model with one coefficient per feature
+pub fn perform_regression_with(freelancers: &[Freelancer], drop_constant_features: bool) -> Result<RateModel, Box<dyn std::error::Error>> {
+    let (x, y) = build_feature_matrix(freelancers)?;
+    let feature_names: Vec<String> = FEATURE_NAMES.iter().map(|name| name.to_string()).collect();
+    if x.nrows() <= feature_names.len() {
+        return Err(RegressionError::InsufficientData { rows: x.nrows(), features: feature_names.len() }.into());
+    }
+    
+    let constant = zero_variance_features(&x, &feature_names);
+    if !constant.is_empty() {
+        eprintln!("Warning: constant features carry no information: {}", constant.join(", "));
+    }
+    let kept: Vec<usize> = (0..feature_names.len())
+        .filter(|&i| !drop_constant_features || !constant.contains(&feature_names[i]))
+        .collect();
+    
+    let mut coefficients = Array1::zeros(feature_names.len());
+    if kept.is_empty() {
+        // Nothing varies, so the best fit is the mean rate
+        let intercept = y.mean().ok_or("cannot fit a regression without data")?;
+        return Ok(RateModel { coefficients, intercept, feature_names });
+    }
+    let x_kept = x.select(Axis(1), &kept);
+    check_collinearity(&x_kept)?;
+    
+    // Create and fit the regression model
+    let dataset = Dataset::new(x_kept, y);
+    let lin_reg = LinearRegression::new();
+    let model = lin_reg.fit(&dataset)?;
+    
+    for (&column, &coefficient) in kept.iter().zip(model.params().iter()) {
+        coefficients[column] = coefficient;
+    }
+    Ok(RateModel {
+        coefficients,
+        intercept: model.intercept(),
+        feature_names,
+    })
+}
+
+/// Per-feature mean and standard deviation used to z-score a feature matrix.
+/// 
+/// # Fields
+/// `means` - Mean of each feature column in the training data
+/// `stds` - Population standard deviation of each column; 1.0 for constant columns
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureScaler {
+    pub means: Array1<f64>,
+    pub stds: Array1<f64>,
+}
+
+impl FeatureScaler {
+    /// Learns the mean and standard deviation of every column.
+    /// 
+    /// Constant columns get a standard deviation of 1.0 so they transform to zeros
+    /// instead of dividing by zero.
+    /// 
+    /// # Arguments: `x` - Feature matrix with one row per freelancer
+    /// 
+    /// # Returns: `Result<FeatureScaler, Box<dyn Error>>` - Scaler with one entry per column
+    pub fn fit(x: &Array2<f64>) -> Result<FeatureScaler, Box<dyn std::error::Error>> {
+        let means = x.mean_axis(Axis(0)).ok_or("cannot standardize an empty matrix")?;
+        let stds = x.std_axis(Axis(0), 0.0).mapv(|s| if s > 1e-12 { s } else { 1.0 });
+        Ok(FeatureScaler { means, stds })
+    }
+    
+    /// Standardizes each column to zero mean and unit variance.
+    /// 
+    /// # Arguments: `x` - Feature matrix with the columns the scaler was fitted on
+    /// 
+    /// # Returns: `Array2<f64>` - `(x - mean) / std` per column
+    pub fn transform(&self, x: &Array2<f64>) -> Array2<f64> {
+        (x - &self.means) / &self.stds
+    }
+    
+    /// Maps standardized features back to their original scale.
+    /// 
+    /// # Arguments: `z` - Standardized feature matrix
+    /// 
+    /// # Returns: `Array2<f64>` - `z * std + mean` per column
+    pub fn inverse_transform(&self, z: &Array2<f64>) -> Array2<f64> {
+        z * &self.stds + &self.means
+    }
+    
+    /// Converts a model fitted on standardized features into one taking raw features.
+    /// 
+    /// Each coefficient is divided by its column's standard deviation and the intercept
+    /// absorbs the shifted means, so both models give the same predictions.
+    /// 
+    /// # Arguments: `model` - Model fitted on `transform`ed features
+    /// 
+    /// # Returns: `RateModel` - Equivalent model taking features on their original scale
+    pub fn unstandardize(&self, model: &RateModel) -> RateModel {
+        let coefficients = &model.coefficients / &self.stds;
+        RateModel {
+            intercept: model.intercept - self.means.dot(&coefficients),
+            coefficients,
+            feature_names: model.feature_names.clone(),
+        }
+    }
+}
+
+/// Performs linear regression on z-scored features.
+/// 
+/// The coefficients are standardized effects: the change in hourly rate for a one
+/// standard deviation change in the feature, comparable across features.
+/// 
+/// # Arguments: `freelancers` - Training data
+/// 
+/// # Returns: `Result<(RateModel, FeatureScaler), Box<dyn Error>>` - Model taking standardized features, and the scaler to apply to new rows
+/// 
+/// # Errors
+/// Returns error unless there are more rows than features, or if the features are collinear
+pub fn perform_standardized_regression(freelancers: &[Freelancer]) -> Result<(RateModel, FeatureScaler), Box<dyn std::error::Error>> {
+    let (x, y) = build_feature_matrix(freelancers)?;
+    if x.nrows() <= FEATURE_NAMES.len() {
+        return Err(RegressionError::InsufficientData { rows: x.nrows(), features: FEATURE_NAMES.len() }.into());
+    }
+    check_collinearity(&x)?;
+    
+    let scaler = FeatureScaler::fit(&x)?;
+    let model = LinearRegression::new().fit(&Dataset::new(scaler.transform(&x), y))?;
+    Ok((
+        RateModel {
+            coefficients: model.params().clone(),
+            intercept: model.intercept(),
+            feature_names: FEATURE_NAMES.iter().map(|name| name.to_string()).collect(),
+        },
+        scaler,
+    ))
+}
+
+/// Lists the features whose column holds the same value in every row.
+/// 
+/// Such features contribute nothing to the fit and make standardization divide by zero.
+/// 
+/// # Arguments: `x` - Feature matrix with one row per freelancer, `names` - Name of each column
+/// 
+/// # Returns: `Vec<String>` - Names of the constant columns, in column order
+pub fn zero_variance_features(x: &Array2<f64>, names: &[String]) -> Vec<String> {
+    x.columns()
+        .into_iter()
+        .zip(names.iter())
+        .filter(|(column, _)| {
+            let first = column.first().copied().unwrap_or(0.0);
+            let scale = first.abs().max(1.0);
+            column.iter().all(|&value| (value - first).abs() <= 1e-12 * scale)
+        })
+        .map(|(_, name)| name.clone())
+        .collect()
+}
+
+/// Computes the Pearson correlation coefficient of two equally long samples.
+/// 
+/// # Arguments: `x` - First sample, `y` - Second sample, paired with `x` by position
+/// 
+/// # Returns: `Option<f64>` - Correlation in -1..=1, or `None` if the lengths differ,
+/// the samples are empty or either one has zero variance
+pub fn pearson_correlation(x: &[f64], y: &[f64]) -> Option<f64> {
+    if x.len() != y.len() || x.is_empty() {
+        return None;
+    }
+    let n = x.len() as f64;
+    let x_mean = x.iter().sum::<f64>() / n;
+    let y_mean = y.iter().sum::<f64>() / n;
+    
+    let (mut covariance, mut x_variance, mut y_variance) = (0.0, 0.0, 0.0);
+    for (a, b) in x.iter().zip(y.iter()) {
+        covariance += (a - x_mean) * (b - y_mean);
+        x_variance += (a - x_mean).powi(2);
+        y_variance += (b - y_mean).powi(2);
+    }
+    if x_variance <= 0.0 || y_variance <= 0.0 {
+        return None;
+    }
+    Some((covariance / (x_variance * y_variance).sqrt()).clamp(-1.0, 1.0))
+}
+
+/// Correlates job success rate and earnings with hourly rate across the dataset.
+/// 
+/// Freelancers without a known rate are skipped.
+/// 
+/// # Arguments: `freelancers` - Slice of Freelancer structs
+/// 
+/// # Returns: `Vec<(String, Option<f64>)>` - Feature name and its correlation with hourly rate; see `pearson_correlation`
+pub fn rate_correlations(freelancers: &[Freelancer]) -> Vec<(String, Option<f64>)> {
+    let known: Vec<&Freelancer> = freelancers.iter().filter(|f| !f.hourly_rate.is_nan()).collect();
+    let rates: Vec<f64> = known.iter().map(|f| f.hourly_rate as f64).collect();
+    let success: Vec<f64> = known.iter().map(|f| f.job_success_rate as f64).collect();
+    let earnings: Vec<f64> = known.iter().map(|f| f.earnings_usd as f64).collect();
+    vec![
+        ("Job Success Rate".to_string(), pearson_correlation(&success, &rates)),
+        ("Earnings USD".to_string(), pearson_correlation(&earnings, &rates)),
+    ]
+}
+
+/// Smallest pivot of the feature correlation matrix accepted as full rank.
+/// 
+/// Pivots shrink toward 0 as a feature becomes a linear combination of the others;
+/// this bound corresponds to a condition number of roughly 1e10.
+const COLLINEARITY_TOLERANCE: f64 = 1e-10;
+
+/// Checks that the design matrix has full column rank once an intercept is included.
+/// 
+/// Columns are centered and scaled into a correlation matrix, which is then reduced
+/// by Gaussian elimination. A constant column, a column that duplicates a linear
+/// combination of others, or fewer rows than needed to identify every coefficient
+/// all show up as a pivot below `COLLINEARITY_TOLERANCE`.
+/// 
+/// # Arguments: `x` - Feature matrix with one row per freelancer
+/// 
+/// # Returns: `Result<(), Box<dyn Error>>` - Ok if the features can be fitted, otherwise a collinearity error
+pub fn check_collinearity(x: &Array2<f64>) -> Result<(), Box<dyn std::error::Error>> {
+    let collinear = || "features are collinear; drop or regularize".into();
+    let (rows, cols) = x.dim();
+    if cols == 0 {
+        return Ok(());
+    }
+    if rows <= cols {
+        return Err(collinear());
+    }
+    
+    let mean = x.mean_axis(Axis(0)).ok_or("cannot check collinearity of an empty matrix")?;
+    let centered = x - &mean;
+    let gram = centered.t().dot(&centered);
+    
+    let scale: Vec<f64> = (0..cols).map(|i| gram[[i, i]].sqrt()).collect();
+    let largest = scale.iter().fold(0.0f64, |m, &v| m.max(v));
+    if scale.iter().any(|&v| v <= COLLINEARITY_TOLERANCE * largest.max(1.0)) {
+        return Err(collinear());
+    }
+    let mut corr = Array2::from_shape_fn((cols, cols), |(i, j)| gram[[i, j]] / (scale[i] * scale[j]));
+    
+    // The correlation matrix is symmetric positive semi-definite, so no pivoting is needed
+    for k in 0..cols {
+        let pivot = corr[[k, k]];
+        if pivot < COLLINEARITY_TOLERANCE {
+            return Err(collinear());
+        }
+        for i in (k + 1)..cols {
+            let factor = corr[[i, k]] / pivot;
+            for j in k..cols {
+                corr[[i, j]] -= factor * corr[[k, j]];
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Encodes freelancers into a feature matrix and a target vector of hourly rates.
+/// 
+/// Freelancers whose hourly rate is missing (NaN) have no target and are skipped.
+/// 
+/// # Arguments: `freelancers` - Slice of Freelancer structs to encode
+/// 
+/// # Returns: `Result<(Array2<f64>, Array1<f64>), Box<dyn Error>>` - One row of features and one target per freelancer with a rate
+pub fn build_feature_matrix(freelancers: &[Freelancer]) -> Result<(Array2<f64>, Array1<f64>), Box<dyn std::error::Error>> {
     // Prepare data structures for features and target
     let mut x_data = Vec::new();
     let mut y_data = Vec::new();
     
     // Process each freelancer's data
-    for freelancer in freelancers {
-        // Convert job success rate from percentage (50-100) to 0-1 range
-        let normalized_success = (freelancer.job_success_rate as f64) / 100.0;
-        
-        // Convert categorical variables to numerical values
-        let job_category_value = match freelancer.job_category.as_str() {
-            "Web Development" => 1.0,
-            "Mobile Development" => 2.0,
-            "Design" => 3.0,
-            "Writing" => 4.0,
-            "Data Science" => 5.0,
-            _ => 0.0,
-        };
-        
-        let experience_value = match freelancer.experience_level.as_str() {
-            "Entry Level" => 1.0,
-            "Intermediate" => 2.0,
-            "Expert" => 3.0,
-            _ => 0.0,
-        };
-        
-        // Combine features into a single vector
-        x_data.push(vec![
-            normalized_success,
-            job_category_value,
-            experience_value,
-        ]);
+    for freelancer in freelancers.iter().filter(|f| !f.hourly_rate.is_nan()) {
+        x_data.push(encode_features(freelancer));
         y_data.push(freelancer.hourly_rate as f64);
     }
     
     // Convert data to ndarray format for the regression model
-    let x: Array2<f64> = Array2::from_shape_vec((x_data.len(), 3), x_data.into_iter().flatten().collect())?;
+    let n_features = FEATURE_NAMES.len();
+    let x: Array2<f64> = Array2::from_shape_vec((x_data.len(), n_features), x_data.into_iter().flatten().collect())?;
     let y: Array1<f64> = Array1::from_vec(y_data);
+    Ok((x, y))
+}
+
+/// Produces exponentially decaying sample weights that favor recent records.
+/// 
+/// Freelancers are assumed to be ordered oldest first, as rows are appended to the
+/// CSV over time, so the last sample is the most recent and gets weight 1.0. A
+/// sample `half_life` positions older gets weight 0.5.
+/// 
+/// # Arguments: `n` - Number of samples, `half_life` - Number of positions over which the weight halves
+/// 
+/// # Returns: `Vec<f64>` - One weight per sample; all 1.0 if `half_life` is not a positive finite number
+pub fn exponential_recency_weights(n: usize, half_life: f64) -> Vec<f64> {
+    if !(half_life > 0.0 && half_life.is_finite()) {
+        return vec![1.0; n];
+    }
+    (0..n)
+        .map(|i| 0.5f64.powf((n - 1 - i) as f64 / half_life))
+        .collect()
+}
+
+/// Performs weighted least squares regression on freelancer data.
+/// 
+/// # Arguments: `freelancers` - Training data, `weights` - Non-negative weight for each freelancer
+/// 
+/// # Returns: `Result<RateModel, Box<dyn Error>>` - Fitted model with one coefficient per feature
+/// 
+/// # Errors
+/// Returns error if the weights do not match the data, are negative or sum to zero,
+/// if the features are collinear, or if the weighted normal equations are singular
+pub fn perform_weighted_regression(freelancers: &[Freelancer], weights: &[f64]) -> Result<RateModel, Box<dyn std::error::Error>> {
+    if weights.len() != freelancers.len() {
+        return Err(format!("expected {} weights, got {}", freelancers.len(), weights.len()).into());
+    }
+    if weights.iter().any(|&w| w < 0.0 || !w.is_finite()) {
+        return Err("weights must be finite and non-negative".into());
+    }
     
-    // Create and fit the regression model
-    let dataset = Dataset::new(x.clone(), y.clone());
-    let lin_reg = LinearRegression::new();
-    let model = lin_reg.fit(&dataset)?;
+    let (x, y) = build_feature_matrix(freelancers)?;
+    check_collinearity(&x)?;
+    // Keep the weights aligned with the rows that have a rate
+    let weights: Array1<f64> = freelancers.iter()
+        .zip(weights.iter())
+        .filter(|(f, _)| !f.hourly_rate.is_nan())
+        .map(|(_, &w)| w)
+        .collect();
+    let (coefficients, intercept) = fit_weighted_least_squares(&x, &y, &weights)?;
+    
+    Ok(RateModel {
+        coefficients,
+        intercept,
+        feature_names: FEATURE_NAMES.iter().map(|name| name.to_string()).collect(),
+    })
+}
+
+/// Fits a regression where newer freelancers influence the fit more.
+/// 
+/// # Arguments: `freelancers` - Training data ordered oldest first, `half_life` - See `exponential_recency_weights`
+/// 
+/// # Returns: `Result<RateModel, Box<dyn Error>>` - Recency-weighted model
+pub fn perform_recency_weighted_regression(freelancers: &[Freelancer], half_life: f64) -> Result<RateModel, Box<dyn std::error::Error>> {
+    let weights = exponential_recency_weights(freelancers.len(), half_life);
+    perform_weighted_regression(freelancers, &weights)
+}
+
+/// Fits a ridge (L2-regularized) regression on freelancer data.
+/// 
+/// Solves `(Xᵀ X + alpha · I) β = Xᵀ y` on mean-centered data, so the intercept is not
+/// penalized. Any positive `alpha` keeps the system solvable when features are
+/// collinear, at the cost of shrinking the coefficients toward zero.
+/// 
+/// # Arguments: `freelancers` - Training data, `alpha` - Regularization strength; 0.0 gives ordinary least squares
+/// 
+/// # Returns: `Result<RateModel, Box<dyn Error>>` - Fitted model with one coefficient per feature
+/// 
+/// # Errors
+/// Returns error if `alpha` is negative or not finite, if there is no data, or if
+/// `alpha` is 0.0 and the features are collinear
+pub fn perform_ridge_regression(freelancers: &[Freelancer], alpha: f64) -> Result<RateModel, Box<dyn std::error::Error>> {
+    if !(alpha >= 0.0 && alpha.is_finite()) {
+        return Err(format!("alpha must be finite and non-negative, got {}", alpha).into());
+    }
+    
+    let (x, y) = build_feature_matrix(freelancers)?;
+    let x_mean = x.mean_axis(Axis(0)).ok_or("cannot fit a regression without data")?;
+    let y_mean = y.mean().ok_or("cannot fit a regression without data")?;
+    let x_centered = &x - &x_mean;
+    let y_centered = &y - y_mean;
+    
+    let gram = x_centered.t().dot(&x_centered) + Array2::<f64>::eye(x.ncols()) * alpha;
+    let rhs = x_centered.t().dot(&y_centered);
+    let coefficients = solve_linear_system(gram, rhs)?;
+    
+    Ok(RateModel {
+        intercept: y_mean - x_mean.dot(&coefficients),
+        coefficients,
+        feature_names: FEATURE_NAMES.iter().map(|name| name.to_string()).collect(),
+    })
+}
+
+/// Solves weighted least squares with an intercept through the normal equations.
+/// 
+/// The data are centered on their weighted means, `(Xᵀ W X) β = Xᵀ W y` is solved
+/// for the coefficients and the intercept is recovered from the means.
+fn fit_weighted_least_squares(x: &Array2<f64>, y: &Array1<f64>, weights: &Array1<f64>) -> Result<(Array1<f64>, f64), Box<dyn std::error::Error>> {
+    let total_weight = weights.sum();
+    if total_weight <= 0.0 {
+        return Err("weights must not all be zero".into());
+    }
+    
+    // Weighted means of each feature and of the target
+    let x_mean = x.t().dot(weights) / total_weight;
+    let y_mean = y.dot(weights) / total_weight;
+    
+    let x_centered = x - &x_mean;
+    let y_centered = y - y_mean;
+    let weighted_x = &x_centered * &weights.view().insert_axis(Axis(1));
+    
+    let gram = weighted_x.t().dot(&x_centered);
+    let rhs = weighted_x.t().dot(&y_centered);
+    let coefficients = solve_linear_system(gram, rhs)?;
+    let intercept = y_mean - x_mean.dot(&coefficients);
+    Ok((coefficients, intercept))
+}
+
+/// Solves `a · x = b` by Gaussian elimination with partial pivoting.
+fn solve_linear_system(mut a: Array2<f64>, mut b: Array1<f64>) -> Result<Array1<f64>, Box<dyn std::error::Error>> {
+    let n = b.len();
+    let scale = a.iter().fold(0.0f64, |m, v| m.max(v.abs())).max(1.0);
+    
+    for col in 0..n {
+        // Pick the row with the largest pivot to keep the elimination stable
+        let pivot_row = (col..n)
+            .max_by(|&i, &j| a[[i, col]].abs().total_cmp(&a[[j, col]].abs()))
+            .unwrap_or(col);
+        if a[[pivot_row, col]].abs() <= 1e-12 * scale {
+            return Err("normal equations are singular".into());
+        }
+        if pivot_row != col {
+            for k in 0..n {
+                a.swap([col, k], [pivot_row, k]);
+            }
+            b.swap(col, pivot_row);
+        }
+        
+        for row in (col + 1)..n {
+            let factor = a[[row, col]] / a[[col, col]];
+            for k in col..n {
+                a[[row, k]] -= factor * a[[col, k]];
+            }
+            b[row] -= factor * b[col];
+        }
+    }
     
-    // Return only model parameters
-    Ok((model.params().clone(), model.intercept()))
+    // Back substitution
+    let mut x = Array1::zeros(n);
+    for row in (0..n).rev() {
+        let tail: f64 = ((row + 1)..n).map(|k| a[[row, k]] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[[row, row]];
+    }
+    Ok(x)
+}
+/// Scores a hand-written feature vector without building a `Freelancer`.
+/// 
+/// # Arguments: `model` - Fitted rate model, `features` - Encoded features in model order
+/// 
+/// # Returns: `Result<f64, Box<dyn Error>>` - `intercept + features · coefficients`
+/// 
+/// # Errors
+/// Returns error if the vector length differs from the model's feature count
+pub fn predict_raw(model: &RateModel, features: &Array1<f64>) -> Result<f64, Box<dyn std::error::Error>> {
+    if features.len() != model.coefficients.len() {
+        return Err(format!(
+            "expected {} features, got {}",
+            model.coefficients.len(),
+            features.len()
+        ).into());
+    }
+    Ok(model.intercept + features.dot(&model.coefficients))
+}
+
+/// How each feature in `FEATURE_NAMES` is encoded.
+pub const FEATURE_ENCODINGS: [&str; 3] = [
+    "percentage / 100",
+    "ordinal 1-5, 0 = unknown",
+    "ordinal 1-3, 0 = unknown",
+];
+
+/// Observed statistics of one encoded feature in the training data.
+/// 
+/// # Fields
+/// `name` - Feature name
+/// `encoding` - Description of how raw values are encoded
+/// `mean` - Mean encoded value in the training data
+/// `min` - Smallest encoded value seen in training
+/// `max` - Largest encoded value seen in training
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureRange {
+    pub name: String,
+    pub encoding: String,
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+/// Describes the expected encoding and trained range of every model feature.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureSpec {
+    pub features: Vec<FeatureRange>,
+}
+
+impl FeatureSpec {
+    /// Records the encoding, mean and range of each feature in the training data.
+    /// 
+    /// # Arguments: `freelancers` - Training data the model was fitted on
+    /// 
+    /// # Returns: `Result<FeatureSpec, Box<dyn Error>>` - Spec with one entry per feature
+    pub fn from_freelancers(freelancers: &[Freelancer]) -> Result<FeatureSpec, Box<dyn std::error::Error>> {
+        let (x, _) = build_feature_matrix(freelancers)?;
+        let features = FEATURE_NAMES.iter()
+            .zip(FEATURE_ENCODINGS.iter())
+            .zip(x.columns())
+            .map(|((name, encoding), column)| FeatureRange {
+                name: name.to_string(),
+                encoding: encoding.to_string(),
+                mean: column.mean().unwrap_or(f64::NAN),
+                min: column.iter().copied().fold(f64::INFINITY, f64::min),
+                max: column.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            })
+            .collect();
+        Ok(FeatureSpec { features })
+    }
+}
+
+/// Checks that hand-written query rows match the trained feature layout and range.
+/// 
+/// # Arguments: `spec` - Trained feature spec, `queries` - One encoded query per row
+/// 
+/// # Returns: `Result<(), Box<dyn Error>>` - Ok if every value lies within its trained range
+/// 
+/// # Errors
+/// Returns error naming the first feature whose value is outside the trained range,
+/// or if the number of columns does not match the spec
+pub fn validate_query(spec: &FeatureSpec, queries: &Array2<f64>) -> Result<(), Box<dyn std::error::Error>> {
+    if queries.ncols() != spec.features.len() {
+        return Err(format!("expected {} features, got {}", spec.features.len(), queries.ncols()).into());
+    }
+    for (row_index, row) in queries.rows().into_iter().enumerate() {
+        for (feature, &value) in spec.features.iter().zip(row.iter()) {
+            if !(value >= feature.min && value <= feature.max) {
+                return Err(format!(
+                    "query {}: {} = {} is outside the trained range {}..={} ({})",
+                    row_index + 1, feature.name, value, feature.min, feature.max, feature.encoding
+                ).into());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks whether a query lies outside what the model saw during training.
+/// 
+/// A query is an extrapolation when its (job category, experience level) pair never
+/// appeared in the training data, or when its job success rate falls outside the
+/// trained range. Predictions for such queries are unreliable.
+/// 
+/// # Arguments: `training_freelancers` - Data the model was fitted on, `query` - Freelancer to predict for
+/// 
+/// # Returns: `bool` - `true` if the prediction would be an extrapolation
+pub fn is_extrapolation(training_freelancers: &[Freelancer], query: &Freelancer) -> bool {
+    let seen_combination = training_freelancers.iter().any(|f| {
+        f.job_category == query.job_category && f.experience_level == query.experience_level
+    });
+    
+    let (min_rate, max_rate) = training_freelancers.iter()
+        .map(|f| f.job_success_rate)
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), rate| (lo.min(rate), hi.max(rate)));
+    let rate_in_range = query.job_success_rate >= min_rate && query.job_success_rate <= max_rate;
+    
+    !(seen_combination && rate_in_range)
+}
+
+/// Greedily adds features in the order that most improves in-sample R-squared.
+/// 
+/// Starting from an intercept-only model, each step fits every remaining feature
+/// together with the ones already chosen and keeps the one with the highest R².
+/// Features that would make the fit singular are skipped.
+/// 
+/// # Arguments: `freelancers` - Training data
+/// 
+/// # Returns: `Result<Vec<(String, f64)>, Box<dyn Error>>` - Each added feature with the R² reached after adding it
+pub fn forward_selection(freelancers: &[Freelancer]) -> Result<Vec<(String, f64)>, Box<dyn std::error::Error>> {
+    let (x, y) = build_feature_matrix(freelancers)?;
+    let actual = y.to_vec();
+    let weights = Array1::ones(y.len());
+    
+    let mut selected: Vec<usize> = Vec::new();
+    let mut steps = Vec::new();
+    
+    while selected.len() < FEATURE_NAMES.len() {
+        let mut best: Option<(usize, f64)> = None;
+        for candidate in (0..FEATURE_NAMES.len()).filter(|c| !selected.contains(c)) {
+            let mut columns = selected.clone();
+            columns.push(candidate);
+            let x_subset = x.select(Axis(1), &columns);
+            
+            let (coefficients, intercept) = match fit_weighted_least_squares(&x_subset, &y, &weights) {
+                Ok(fit) => fit,
+                Err(_) => continue,
+            };
+            let predicted = (x_subset.dot(&coefficients) + intercept).to_vec();
+            let r_squared = compute_error_metrics(&actual, &predicted).r_squared;
+            
+            let better = match best {
+                Some((_, best_r_squared)) => r_squared > best_r_squared,
+                None => true,
+            };
+            if better {
+                best = Some((candidate, r_squared));
+            }
+        }
+        
+        match best {
+            Some((feature, r_squared)) => {
+                selected.push(feature);
+                steps.push((FEATURE_NAMES[feature].to_string(), r_squared));
+            }
+            None => break,
+        }
+    }
+    Ok(steps)
+}
+
+/// Splits freelancers into a training set and a test set after a seeded shuffle.
+/// 
+/// The same seed always produces the same split. `test_fraction` is clamped to
+/// `[0.0, 1.0]`, so 0.0 puts everything in the training set and 1.0 everything in
+/// the test set.
+/// 
+/// # Arguments: `freelancers` - Data to split, `test_fraction` - Share of rows held out, `seed` - Shuffle seed
+/// 
+/// # Returns: `(Vec<Freelancer>, Vec<Freelancer>)` - Training set and test set
+pub fn train_test_split(freelancers: &[Freelancer], test_fraction: f64, seed: u64) -> (Vec<Freelancer>, Vec<Freelancer>) {
+    let mut indices: Vec<usize> = (0..freelancers.len()).collect();
+    indices.shuffle(&mut StdRng::seed_from_u64(seed));
+    
+    let fraction = if test_fraction.is_nan() { 0.0 } else { test_fraction.clamp(0.0, 1.0) };
+    let test_len = (freelancers.len() as f64 * fraction).round() as usize;
+    let (test_indices, train_indices) = indices.split_at(test_len);
+    
+    let collect = |selected: &[usize]| selected.iter().map(|&i| freelancers[i].clone()).collect();
+    (collect(train_indices), collect(test_indices))
+}
+
+/// Share of the data held out when comparing target transforms.
+const COMPARISON_TEST_FRACTION: f64 = 0.25;
+
+/// Compares fitting the raw hourly rate against fitting `ln(hourly_rate)`.
+/// 
+/// Both models are trained on the same seeded split. The log model's predictions
+/// are back-transformed with `exp` so that both RMSEs are on the dollar scale.
+/// Rows with a non-positive rate are left out of the log model's training data, and
+/// test rows without a known (finite) rate are not scored.
+/// 
+/// # Arguments: `freelancers` - Data to split and fit, `seed` - Seed for `train_test_split`
+/// 
+/// # Returns: `Result<Vec<(String, f64)>, Box<dyn Error>>` - Test RMSE of the "linear" and "log-linear" models
+pub fn compare_target_transforms(freelancers: &[Freelancer], seed: u64) -> Result<Vec<(String, f64)>, Box<dyn std::error::Error>> {
+    let (train, test) = train_test_split(freelancers, COMPARISON_TEST_FRACTION, seed);
+    let test: Vec<Freelancer> = test.into_iter().filter(|f| f.hourly_rate.is_finite()).collect();
+    if train.is_empty() || test.is_empty() {
+        return Err("not enough data to hold out a test set".into());
+    }
+    let actual: Vec<f64> = test.iter().map(|f| f.hourly_rate as f64).collect();
+    
+    let linear = perform_regression(&train)?;
+    let linear_predicted: Vec<f64> = test.iter().map(|f| linear.predict(&encode_features(f))).collect();
+    
+    let log_train: Vec<Freelancer> = train.iter()
+        .filter(|f| f.hourly_rate > 0.0)
+        .map(|f| Freelancer { hourly_rate: f.hourly_rate.ln(), ..f.clone() })
+        .collect();
+    let log_linear = perform_regression(&log_train)?;
+    let log_predicted: Vec<f64> = test.iter().map(|f| log_linear.predict(&encode_features(f)).exp()).collect();
+    
+    Ok(vec![
+        ("linear".to_string(), compute_error_metrics(&actual, &linear_predicted).rmse),
+        ("log-linear".to_string(), compute_error_metrics(&actual, &log_predicted).rmse),
+    ])
+}
+
+/// Estimates the dollar change in hourly rate per percentage point of job success rate.
+/// 
+/// `encode_features` divides the success rate by 100, so the model coefficient is
+/// per unit of the 0-1 fraction; dividing it by 100 gives $/percentage point with
+/// the other features held constant.
+/// 
+/// # Arguments: `model` - Fitted rate model
+/// 
+/// # Returns: `f64` - USD per percentage point, or NaN if the model has no "Job Success Rate" feature
+pub fn success_rate_elasticity(model: &RateModel) -> f64 {
+    model.feature_names.iter()
+        .position(|name| name == FEATURE_NAMES[0])
+        .map(|i| model.coefficients[i] / 100.0)
+        .unwrap_or(f64::NAN)
+}
+
+/// Pseudo-count pulling each category's mean rate toward the global mean in `target_encode`.
+pub const TARGET_ENCODING_SMOOTHING: f64 = 10.0;
+
+/// Maps each category value to its smoothed mean hourly rate.
+/// 
+/// The encoded value is `(n·mean + m·global_mean) / (n + m)`, where `n` is the
+/// number of freelancers with that value and `m` is `TARGET_ENCODING_SMOOTHING`,
+/// so rare categories stay close to the global mean instead of memorizing a few rates.
+/// Freelancers without a known (finite) hourly rate are skipped.
+/// 
+/// # Arguments: `freelancers` - Training data, `accessor` - Category to encode, e.g. `|f| f.job_category.clone()`
+/// 
+/// # Returns: `HashMap<String, f64>` - Smoothed mean hourly rate for every category value
+pub fn target_encode<F>(freelancers: &[Freelancer], accessor: F) -> HashMap<String, f64>
+where
+    F: Fn(&Freelancer) -> String,
+{
+    let known: Vec<&Freelancer> = freelancers.iter().filter(|f| f.hourly_rate.is_finite()).collect();
+    if known.is_empty() {
+        return HashMap::new();
+    }
+    let global_mean = known.iter().map(|f| f.hourly_rate as f64).sum::<f64>() / known.len() as f64;
+    
+    let mut groups: HashMap<String, (f64, usize)> = HashMap::new();
+    for freelancer in known {
+        let (sum, count) = groups.entry(accessor(freelancer)).or_insert((0.0, 0));
+        *sum += freelancer.hourly_rate as f64;
+        *count += 1;
+    }
+    
+    groups.into_iter()
+        .map(|(value, (sum, count))| {
+            let smoothed = (sum + TARGET_ENCODING_SMOOTHING * global_mean) / (count as f64 + TARGET_ENCODING_SMOOTHING);
+            (value, smoothed)
+        })
+        .collect()
+}
+
+/// A rate model whose job category feature is target encoded instead of ordinal.
+/// 
+/// # Fields
+/// `model` - Fitted linear model over success rate, encoded category and experience level
+/// `category_encoding` - Smoothed mean rate of each job category seen in training
+/// `global_mean` - Mean training rate, used for categories not seen in training
+pub struct TargetEncodedModel {
+    pub model: RateModel,
+    pub category_encoding: HashMap<String, f64>,
+    pub global_mean: f64,
+}
+
+impl TargetEncodedModel {
+    /// Encodes a freelancer with the target-encoded job category.
+    /// 
+    /// # Returns: `Vec<f64>` - Success rate (0-1), encoded category, experience level (ordinal)
+    pub fn encode(&self, freelancer: &Freelancer) -> Vec<f64> {
+        let mut features = encode_features(freelancer);
+        features[1] = self.category_encoding.get(&freelancer.job_category).copied().unwrap_or(self.global_mean);
+        features
+    }
+    
+    /// Predicts the hourly rate of a freelancer.
+    pub fn predict_freelancer(&self, freelancer: &Freelancer) -> f64 {
+        self.model.predict(&self.encode(freelancer))
+    }
+}
+
+/// Fits the regression with the job category replaced by its target encoding.
+/// 
+/// Target encoding gives one column however many categories exist, unlike one-hot
+/// encoding, and orders categories by their typical rate rather than arbitrarily.
+/// Freelancers without a known (finite) hourly rate are skipped.
+/// 
+/// # Arguments: `freelancers` - Training data
+/// 
+/// # Returns: `Result<TargetEncodedModel, Box<dyn Error>>` - Fitted model with its category encoding
+pub fn perform_target_encoded_regression(freelancers: &[Freelancer]) -> Result<TargetEncodedModel, Box<dyn std::error::Error>> {
+    let training: Vec<Freelancer> = freelancers.iter().filter(|f| f.hourly_rate.is_finite()).cloned().collect();
+    let freelancers = training.as_slice();
+    if freelancers.is_empty() {
+        return Err("cannot fit a regression without data".into());
+    }
+    let category_encoding = target_encode(freelancers, |f| f.job_category.clone());
+    let global_mean = freelancers.iter().map(|f| f.hourly_rate as f64).sum::<f64>() / freelancers.len() as f64;
+    let mut encoded = TargetEncodedModel {
+        model: RateModel {
+            coefficients: Array1::zeros(FEATURE_NAMES.len()),
+            intercept: 0.0,
+            feature_names: vec![
+                FEATURE_NAMES[0].to_string(),
+                format!("{} (target encoded)", FEATURE_NAMES[1]),
+                FEATURE_NAMES[2].to_string(),
+            ],
+        },
+        category_encoding,
+        global_mean,
+    };
+    
+    let rows: Vec<f64> = freelancers.iter().flat_map(|f| encoded.encode(f)).collect();
+    let x = Array2::from_shape_vec((freelancers.len(), FEATURE_NAMES.len()), rows)?;
+    let y: Array1<f64> = freelancers.iter().map(|f| f.hourly_rate as f64).collect();
+    check_collinearity(&x)?;
+    
+    let (coefficients, intercept) = fit_weighted_least_squares(&x, &y, &Array1::ones(y.len()))?;
+    encoded.model.coefficients = coefficients;
+    encoded.model.intercept = intercept;
+    Ok(encoded)
+}
+
+/// Fills missing hourly rates with the model's prediction.
+/// 
+/// A rate is missing when it is NaN, which is how the loader reads a blank
+/// `Hourly_Rate` cell.
+/// 
+/// # Arguments: `model` - Fitted rate model, `freelancers` - Freelancers to fill in place
+/// 
+/// # Returns: `Vec<usize>` - Indices of the freelancers whose rate was imputed
+pub fn impute_rates(model: &RateModel, freelancers: &mut [Freelancer]) -> Vec<usize> {
+    let mut imputed = Vec::new();
+    for (i, freelancer) in freelancers.iter_mut().enumerate() {
+        if freelancer.hourly_rate.is_nan() {
+            freelancer.hourly_rate = model.predict(&encode_features(freelancer)) as f32;
+            imputed.push(i);
+        }
+    }
+    imputed
+}
+
+/// Breaks a prediction down into the contribution of each feature.
+/// 
+/// # Arguments: `model` - Fitted rate model, `freelancer` - Freelancer to explain
+/// 
+/// # Returns: `Vec<(String, f64)>` - Feature name and `coefficient * value` for every feature
+pub fn explain_prediction(model: &RateModel, freelancer: &Freelancer) -> Vec<(String, f64)> {
+    let features = encode_features(freelancer);
+    model.feature_names.iter()
+        .zip(model.coefficients.iter().zip(features.iter()))
+        .map(|(name, (c, x))| (name.clone(), c * x))
+        .collect()
+}
+
+/// Describes a prediction and its feature contributions as a sentence.
+/// 
+/// Dollar amounts are rounded to whole dollars (cents below $1) and features that
+/// contribute nothing (for example unknown categories) are left out.
+/// 
+/// # Arguments: `model` - Fitted rate model, `freelancer` - Freelancer to explain
+/// 
+/// # Returns: `String` - e.g. "Predicted $48/hr; your Expert experience adds $22 and your 95% success rate adds $6."
+pub fn explain_prediction_text(model: &RateModel, freelancer: &Freelancer) -> String {
+    let predicted = model.predict(&encode_features(freelancer));
+    
+    let phrases: Vec<String> = explain_prediction(model, freelancer)
+        .into_iter()
+        .filter(|(_, contribution)| *contribution != 0.0)
+        .map(|(name, contribution)| {
+            let verb = if contribution >= 0.0 { "adds" } else { "subtracts" };
+            format!("{} {} {}", describe_feature(&name, freelancer), verb, format_dollars(contribution.abs()))
+        })
+        .collect();
+    
+    if phrases.is_empty() {
+        return format!("Predicted ${:.0}/hr.", predicted);
+    }
+    format!("Predicted ${:.0}/hr; {}.", predicted, join_phrases(&phrases))
+}
+
+/// Names a feature in terms of the freelancer's own value for it.
+fn describe_feature(name: &str, freelancer: &Freelancer) -> String {
+    match name {
+        "Job Success Rate" => format!("your {:.0}% success rate", freelancer.job_success_rate),
+        "Job Category" => format!("your {} category", freelancer.job_category),
+        "Experience Level" => format!("your {} experience", freelancer.experience_level),
+        _ => format!("your {}", name),
+    }
+}
+
+/// Formats a non-negative dollar amount, keeping cents only below $1.
+fn format_dollars(amount: f64) -> String {
+    if amount < 1.0 {
+        format!("${:.2}", amount)
+    } else {
+        format!("${:.0}", amount)
+    }
+}
+
+/// Joins phrases as "a", "a and b" or "a, b and c".
+fn join_phrases(phrases: &[String]) -> String {
+    match phrases.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+        None => String::new(),
+    }
 }
 
 
 /// Creates a simple test dataset with two freelancers
+#[cfg(test)]
 fn create_test_freelancers() -> Vec<Freelancer> {
     vec![
-        Freelancer {
-            id: 1,
-            job_category: "Web Development".to_string(),
-            platform: "Upwork".to_string(),
-            experience_level: "Expert".to_string(),
-            client_region: "North America".to_string(),
-            earnings_usd: 5000.0,
-            hourly_rate: 50.0,
-            job_success_rate: 95.0,
-        },
-        Freelancer {
-            id: 2,
-            job_category: "Design".to_string(),
-            platform: "Fiverr".to_string(),
-            experience_level: "Entry Level".to_string(),
-            client_region: "Europe".to_string(),
-            earnings_usd: 1000.0,
-            hourly_rate: 20.0,
-            job_success_rate: 75.0,
-        },
+        FreelancerBuilder::new()
+            .id(1)
+            .experience_level("Expert")
+            .client_region("North America")
+            .earnings_usd(5000.0)
+            .hourly_rate(50.0)
+            .job_success_rate(95.0)
+            .build(),
+        FreelancerBuilder::new()
+            .id(2)
+            .job_category("Design")
+            .platform("Fiverr")
+            .experience_level("Entry Level")
+            .client_region("Europe")
+            .hourly_rate(20.0)
+            .job_success_rate(75.0)
+            .build(),
     ]
 }
 
+/// Five freelancers with enough spread to identify every regression coefficient
+#[cfg(test)]
+fn create_identifiable_freelancers() -> Vec<Freelancer> {
+    let mut freelancers = create_test_freelancers();
+    for (i, (rate, success, level)) in [(35.0, 85.0, "Intermediate"), (60.0, 98.0, "Expert"), (25.0, 60.0, "Intermediate")].iter().enumerate() {
+        freelancers.push(Freelancer {
+            id: 3 + i as u32,
+            job_category: if i == 1 { "Design".to_string() } else { "Writing".to_string() },
+            platform: "Upwork".to_string(),
+            experience_level: level.to_string(),
+            client_region: "Asia".to_string(),
+            earnings_usd: 2000.0,
+            hourly_rate: *rate,
+            job_success_rate: *success,
+        });
+    }
+    freelancers
+}
+
 /// Tests basic regression functionality
 #[test]
 fn test_basic_regression() {
-    let freelancers = create_test_freelancers();
+    let mut freelancers = create_test_freelancers();
+    // Two freelancers cannot identify three coefficients, so add two more
+    for (i, (level, success)) in [("Intermediate", 85.0), ("Expert", 70.0)].iter().enumerate() {
+        freelancers.push(Freelancer {
+            id: 3 + i as u32,
+            job_category: "Writing".to_string(),
+            experience_level: level.to_string(),
+            hourly_rate: 30.0 + 5.0 * i as f32,
+            job_success_rate: *success,
+            ..Default::default()
+        });
+    }
     let result = perform_regression(&freelancers);
     
     // Verify regression runs without error
     assert!(result.is_ok());
     
-    let (coefficients, intercept) = result.unwrap();
+    let model = result.unwrap();
     
     // Verify we get the expected number of coefficients
-    assert_eq!(coefficients.len(), 3);
+    assert_eq!(model.coefficients.len(), 3);
     
     // Verify coefficients and intercept are valid numbers
-    assert!(intercept.is_finite());
-    for &coef in coefficients.iter() {
+    assert!(model.intercept.is_finite());
+    for &coef in model.coefficients.iter() {
         assert!(coef.is_finite());
     }
-}
\ No newline at end of file
+}
+
+/// Tests that the explanation text mentions every contributing feature
+#[test]
+fn test_explain_prediction_text() {
+    let freelancers = create_test_freelancers();
+    let model = RateModel {
+        coefficients: Array1::from_vec(vec![20.0, 1.0, 7.0]),
+        intercept: 5.0,
+        feature_names: FEATURE_NAMES.iter().map(|name| name.to_string()).collect(),
+    };
+    
+    // 5 + 20 * 0.95 + 1 * 1 + 7 * 3 = 46
+    let text = explain_prediction_text(&model, &freelancers[0]);
+    assert!(text.starts_with("Predicted $46/hr"));
+    assert!(text.contains("your 95% success rate adds $19"));
+    assert!(text.contains("your Web Development category adds $1"));
+    assert!(text.contains("your Expert experience adds $21"));
+}
+
+/// Tests raw feature scoring with correct and incorrect vector lengths
+#[test]
+fn test_predict_raw() {
+    let model = RateModel {
+        coefficients: Array1::from_vec(vec![20.0, 1.0, 7.0]),
+        intercept: 5.0,
+        feature_names: FEATURE_NAMES.iter().map(|name| name.to_string()).collect(),
+    };
+    
+    let prediction = predict_raw(&model, &Array1::from_vec(vec![0.5, 2.0, 1.0])).unwrap();
+    assert!((prediction - 24.0).abs() < 1e-9);
+    
+    let error = predict_raw(&model, &Array1::from_vec(vec![0.5, 2.0])).unwrap_err();
+    assert!(error.to_string().contains("expected 3 features, got 2"));
+}
+
+/// Tests that recency weights decay toward older samples
+#[test]
+fn test_exponential_recency_weights() {
+    let weights = exponential_recency_weights(5, 2.0);
+    assert_eq!(weights.len(), 5);
+    assert_eq!(weights[4], 1.0);
+    assert!((weights[2] - 0.5).abs() < 1e-12);
+    assert!(weights.windows(2).all(|w| w[0] < w[1]));
+}
+
+/// Tests that weighted regression with equal weights matches ordinary least squares
+#[test]
+fn test_weighted_regression_matches_unweighted() {
+    let freelancers = create_identifiable_freelancers();
+    
+    let ordinary = perform_regression(&freelancers).unwrap();
+    let weighted = perform_weighted_regression(&freelancers, &vec![1.0; freelancers.len()]).unwrap();
+    assert!((ordinary.intercept - weighted.intercept).abs() < 1e-6);
+    for (a, b) in ordinary.coefficients.iter().zip(weighted.coefficients.iter()) {
+        assert!((a - b).abs() < 1e-6);
+    }
+    
+    assert!(perform_recency_weighted_regression(&freelancers, 2.0).is_ok());
+    assert!(perform_weighted_regression(&freelancers, &[1.0]).is_err());
+}
+
+/// Tests that a query outside the trained feature range is rejected
+#[test]
+fn test_validate_query_out_of_range() {
+    let mut freelancers = create_test_freelancers();
+    freelancers[1].experience_level = "Intermediate".to_string();
+    freelancers.push(Freelancer {
+        id: 3,
+        job_category: "Writing".to_string(),
+        platform: "Upwork".to_string(),
+        experience_level: "Entry Level".to_string(),
+        client_region: "Asia".to_string(),
+        earnings_usd: 800.0,
+        hourly_rate: 15.0,
+        job_success_rate: 60.0,
+    });
+    let spec = FeatureSpec::from_freelancers(&freelancers).unwrap();
+    assert_eq!(spec.features[2].min, 1.0);
+    assert_eq!(spec.features[2].max, 3.0);
+    
+    let valid = Array2::from_shape_vec((1, 3), vec![0.8, 3.0, 2.0]).unwrap();
+    assert!(validate_query(&spec, &valid).is_ok());
+    
+    let out_of_range = Array2::from_shape_vec((1, 3), vec![0.8, 3.0, 5.0]).unwrap();
+    let error = validate_query(&spec, &out_of_range).unwrap_err();
+    assert!(error.to_string().contains("Experience Level"));
+}
+
+/// Tests that the feature driving the hourly rate is selected first
+#[test]
+fn test_forward_selection_picks_most_predictive_first() {
+    let rows = [
+        (60.0, "Web Development", "Expert"),
+        (70.0, "Design", "Entry Level"),
+        (80.0, "Writing", "Intermediate"),
+        (90.0, "Web Development", "Entry Level"),
+        (65.0, "Writing", "Expert"),
+        (95.0, "Design", "Intermediate"),
+    ];
+    let freelancers: Vec<Freelancer> = rows.iter()
+        .enumerate()
+        .map(|(i, (success, category, level))| Freelancer {
+            id: i as u32,
+            job_category: category.to_string(),
+            platform: "Upwork".to_string(),
+            experience_level: level.to_string(),
+            client_region: "Asia".to_string(),
+            earnings_usd: 1000.0,
+            // The rate depends on the success rate alone
+            hourly_rate: *success * 0.5,
+            job_success_rate: *success,
+        })
+        .collect();
+    
+    let steps = forward_selection(&freelancers).unwrap();
+    assert_eq!(steps.len(), 3);
+    assert_eq!(steps[0].0, "Job Success Rate");
+    assert!((steps[0].1 - 1.0).abs() < 1e-9);
+    assert!(steps.windows(2).all(|w| w[1].1 >= w[0].1 - 1e-9));
+}
+
+/// Twenty freelancers whose rate rises with success rate and experience level
+/// 
+/// Rows for which `rate_missing(index)` is true get a NaN (blank) hourly rate.
+#[cfg(test)]
+fn create_comparison_freelancers(rate_missing: impl Fn(usize) -> bool) -> Vec<Freelancer> {
+    let categories = ["Web Development", "Design", "Writing", "Data Science"];
+    let levels = ["Entry Level", "Intermediate", "Expert"];
+    (0..20)
+        .map(|i| {
+            let level = i % 3;
+            let success = 60.0 + (i * 7 % 40) as f32;
+            let rate = 10.0 + 0.3 * success + 8.0 * level as f32 + (i % 5) as f32;
+            FreelancerBuilder::new()
+                .id(i as u32)
+                .job_category(categories[i % 4])
+                .experience_level(levels[level])
+                .client_region("Asia")
+                .hourly_rate(if rate_missing(i) { f32::NAN } else { rate })
+                .job_success_rate(success)
+                .build()
+        })
+        .collect()
+}
+
+/// Tests that both target transforms report a finite test RMSE
+#[test]
+fn test_compare_target_transforms() {
+    let freelancers = create_comparison_freelancers(|_| false);
+    
+    let results = compare_target_transforms(&freelancers, 42).unwrap();
+    let names: Vec<&str> = results.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["linear", "log-linear"]);
+    assert!(results.iter().all(|(_, rmse)| rmse.is_finite() && *rmse >= 0.0));
+}
+
+/// Tests that unseen category/experience pairs and out-of-range success rates are flagged
+#[test]
+fn test_is_extrapolation() {
+    let freelancers = create_test_freelancers();
+    
+    let seen = Freelancer { job_success_rate: 80.0, ..freelancers[0].clone() };
+    assert!(!is_extrapolation(&freelancers, &seen));
+    
+    // Both parts occur in training, but never together
+    let unseen_pair = Freelancer { experience_level: "Entry Level".to_string(), ..seen.clone() };
+    assert!(is_extrapolation(&freelancers, &unseen_pair));
+    
+    let out_of_range = Freelancer { job_success_rate: 99.0, ..seen };
+    assert!(is_extrapolation(&freelancers, &out_of_range));
+}
+
+/// Tests that duplicated feature columns and undersized data give the collinearity error
+#[test]
+fn test_check_collinearity() {
+    let independent = Array2::from_shape_vec((4, 2), vec![1.0, 0.0, 2.0, 1.0, 3.0, 0.0, 4.0, 3.0]).unwrap();
+    assert!(check_collinearity(&independent).is_ok());
+    
+    let duplicated = Array2::from_shape_vec((4, 2), vec![1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0]).unwrap();
+    let error = check_collinearity(&duplicated).unwrap_err();
+    assert_eq!(error.to_string(), "features are collinear; drop or regularize");
+    
+    // Two rows cannot identify three coefficients plus an intercept
+    let (x, _) = build_feature_matrix(&create_test_freelancers()).unwrap();
+    let error = check_collinearity(&x).unwrap_err();
+    assert_eq!(error.to_string(), "features are collinear; drop or regularize");
+}
+
+/// Tests that a constant column is reported and dropped with a zero coefficient
+#[test]
+fn test_zero_variance_features() {
+    let names: Vec<String> = FEATURE_NAMES.iter().map(|name| name.to_string()).collect();
+    let x = Array2::from_shape_vec((3, 3), vec![0.9, 1.0, 2.0, 0.8, 3.0, 2.0, 0.7, 4.0, 2.0]).unwrap();
+    assert_eq!(zero_variance_features(&x, &names), vec!["Experience Level".to_string()]);
+    
+    // Every freelancer is an expert
+    let freelancers: Vec<Freelancer> = [(95.0, "Web Development", 50.0), (75.0, "Design", 30.0), (85.0, "Writing", 35.0), (65.0, "Design", 25.0)]
+        .iter()
+        .map(|&(success, category, rate)| Freelancer {
+            job_category: category.to_string(),
+            experience_level: "Expert".to_string(),
+            hourly_rate: rate,
+            job_success_rate: success,
+            ..Default::default()
+        })
+        .collect();
+    let model = perform_regression(&freelancers).unwrap();
+    assert_eq!(model.coefficients.len(), 3);
+    assert_eq!(model.coefficients[2], 0.0);
+    assert!(model.coefficients[0] != 0.0);
+    assert!(perform_regression_with(&freelancers, false).is_err());
+}
+
+/// Tests that the elasticity is the success-rate coefficient per percentage point
+#[test]
+fn test_success_rate_elasticity() {
+    let model = RateModel {
+        coefficients: Array1::from_vec(vec![40.0, 1.0, 5.0]),
+        intercept: 10.0,
+        feature_names: FEATURE_NAMES.iter().map(|name| name.to_string()).collect(),
+    };
+    assert!((success_rate_elasticity(&model) - 0.4).abs() < 1e-12);
+    
+    // Raising the success rate by one point changes the prediction by the elasticity
+    let base = Freelancer { job_success_rate: 80.0, ..Default::default() };
+    let better = Freelancer { job_success_rate: 81.0, ..Default::default() };
+    let change = model.predict(&encode_features(&better)) - model.predict(&encode_features(&base));
+    assert!((change - success_rate_elasticity(&model)).abs() < 1e-9);
+}
+
+/// Tests that target encoding yields the smoothed group mean and supports a regression fit
+#[test]
+fn test_target_encode() {
+    let rows = [
+        ("Design", "Entry Level", 70.0, 20.0),
+        ("Design", "Intermediate", 80.0, 30.0),
+        ("Web Development", "Expert", 90.0, 50.0),
+        ("Web Development", "Intermediate", 85.0, 60.0),
+        ("Writing", "Entry Level", 95.0, 35.0),
+        ("Writing", "Expert", 60.0, 45.0),
+    ];
+    let freelancers: Vec<Freelancer> = rows.iter()
+        .map(|&(category, level, success, rate)| Freelancer {
+            job_category: category.to_string(),
+            experience_level: level.to_string(),
+            job_success_rate: success,
+            hourly_rate: rate,
+            ..Default::default()
+        })
+        .collect();
+    
+    // Global mean is 40 and Design's mean is 25, pulled toward 40 by 10 pseudo-counts
+    let encoding = target_encode(&freelancers, |f| f.job_category.clone());
+    let expected = (2.0 * 25.0 + TARGET_ENCODING_SMOOTHING * 40.0) / (2.0 + TARGET_ENCODING_SMOOTHING);
+    assert!((encoding["Design"] - expected).abs() < 1e-9);
+    assert_eq!(encoding.len(), 3);
+    
+    let model = perform_target_encoded_regression(&freelancers).unwrap();
+    assert!(model.predict_freelancer(&freelancers[0]).is_finite());
+    let unseen = Freelancer { job_category: "Data Science".to_string(), ..freelancers[0].clone() };
+    assert_eq!(model.encode(&unseen)[1], 40.0);
+}
+
+/// Tests that a missing rate is skipped in training and filled with the prediction
+#[test]
+fn test_impute_rates() {
+    let csv = "Freelancer_ID,Job_Category,Platform,Experience_Level,Client_Region,Earnings_USD,Hourly_Rate,Job_Success_Rate\n\
+               1,Web Development,Upwork,Expert,USA,5000,50.0,95.0\n\
+               2,Design,Fiverr,Entry Level,Europe,1000,20.0,75.0\n\
+               3,Writing,Upwork,Intermediate,Asia,2000,30.0,85.0\n\
+               4,Writing,Upwork,Expert,Asia,2500,35.0,70.0\n\
+               5,Design,Upwork,Expert,Asia,3000,,90.0\n";
+    let mut freelancers = crate::data_loader::read_freelancers(csv.as_bytes()).unwrap();
+    assert!(freelancers[4].hourly_rate.is_nan());
+    
+    let model = perform_regression(&freelancers).unwrap();
+    let expected = model.predict(&encode_features(&freelancers[4])) as f32;
+    
+    assert_eq!(impute_rates(&model, &mut freelancers), vec![4]);
+    assert_eq!(freelancers[4].hourly_rate, expected);
+    assert_eq!(freelancers[0].hourly_rate, 50.0);
+    assert!(impute_rates(&model, &mut freelancers).is_empty());
+}
+
+/// Tests that single-row and batch predictions match the manual dot product
+#[test]
+fn test_predict_batch() {
+    let model = RateModel {
+        coefficients: Array1::from_vec(vec![10.0, 2.0, 5.0]),
+        intercept: 3.0,
+        feature_names: FEATURE_NAMES.iter().map(|s| s.to_string()).collect(),
+    };
+    let x = Array2::from_shape_vec((2, 3), vec![0.9, 1.0, 3.0, 0.5, 4.0, 1.0]).unwrap();
+    
+    let manual = 3.0 + 0.9 * 10.0 + 1.0 * 2.0 + 3.0 * 5.0;
+    assert!((model.predict(&[0.9, 1.0, 3.0]) - manual).abs() < 1e-12);
+    let batch = model.predict_batch(&x);
+    assert_eq!(batch.len(), 2);
+    for (row, prediction) in x.rows().into_iter().zip(batch.iter()) {
+        assert!((prediction - model.predict(&row.to_vec())).abs() < 1e-12);
+    }
+}
+
+/// Tests one-hot matrix width, the all-zero encoding of unknown categories and the fitted effects
+#[test]
+fn test_one_hot_regression() {
+    let rows = [
+        ("Design", "Expert", 90.0, 70.0),
+        ("Design", "Intermediate", 80.0, 40.0),
+        ("Writing", "Expert", 70.0, 60.0),
+        ("Writing", "Intermediate", 95.0, 30.0),
+        ("Web Development", "Expert", 85.0, 80.0),
+        ("Web Development", "Intermediate", 60.0, 50.0),
+        ("Web Development", "Expert", 75.0, 80.0),
+    ];
+    let freelancers: Vec<Freelancer> = rows.iter()
+        .map(|&(category, experience, success, rate)| Freelancer {
+            job_category: category.to_string(),
+            experience_level: experience.to_string(),
+            job_success_rate: success,
+            hourly_rate: rate,
+            ..Default::default()
+        })
+        .collect();
+    
+    let encoder = OneHotEncoder::from_freelancers(&freelancers);
+    let x = encoder.build_matrix(&freelancers).unwrap();
+    assert_eq!(x.ncols(), 1 + 3 + 2);
+    assert_eq!(encoder.feature_names()[3], "Job Category = Writing");
+    
+    let unknown = Freelancer { job_category: "Astrology".to_string(), ..freelancers[0].clone() };
+    assert_eq!(&encoder.encode(&unknown)[1..4], &[0.0, 0.0, 0.0]);
+    
+    let (model, _) = perform_one_hot_regression(&freelancers).unwrap();
+    assert_eq!(model.coefficients.len(), model.feature_names.len());
+    // Design and Expert are the reference levels; the rates are exactly additive and ignore success
+    assert_eq!(model.coefficients[1], 0.0);
+    assert!((model.coefficients[2] - 10.0).abs() < 1e-9);
+    assert!((model.coefficients[3] + 10.0).abs() < 1e-9);
+    assert!((model.coefficients[5] + 30.0).abs() < 1e-9);
+    assert!(model.coefficients[0].abs() < 1e-9);
+}
+
+/// Tests that train_test_split is reproducible, disjoint, complete and handles the 0.0 and 1.0 fractions
+#[test]
+fn test_train_test_split() {
+    let freelancers: Vec<Freelancer> = (0..20).map(|id| Freelancer { id, ..Default::default() }).collect();
+    let ids = |set: &[Freelancer]| set.iter().map(|f| f.id).collect::<Vec<u32>>();
+    
+    let (train, test) = train_test_split(&freelancers, 0.3, 7);
+    let (train_again, test_again) = train_test_split(&freelancers, 0.3, 7);
+    assert_eq!(ids(&train), ids(&train_again));
+    assert_eq!(ids(&test), ids(&test_again));
+    assert_eq!(test.len(), 6);
+    
+    let mut all: Vec<u32> = ids(&train).into_iter().chain(ids(&test)).collect();
+    all.sort_unstable();
+    assert_eq!(all, (0..20).collect::<Vec<u32>>());
+    
+    let (train, test) = train_test_split(&freelancers, 0.0, 7);
+    assert_eq!((train.len(), test.len()), (20, 0));
+    let (train, test) = train_test_split(&freelancers, 1.0, 7);
+    assert_eq!((train.len(), test.len()), (0, 20));
+}
+
+/// Tests that empty and too-small datasets fail with RegressionError::InsufficientData
+#[test]
+fn test_regression_insufficient_data() {
+    let error = perform_regression(&[]).err().unwrap();
+    assert_eq!(
+        error.downcast_ref::<RegressionError>(),
+        Some(&RegressionError::InsufficientData { rows: 0, features: 3 })
+    );
+    
+    let error = perform_regression(&create_test_freelancers()).err().unwrap();
+    assert_eq!(
+        error.downcast_ref::<RegressionError>(),
+        Some(&RegressionError::InsufficientData { rows: 2, features: 3 })
+    );
+    
+    // As many rows as features leaves nothing for the intercept
+    let mut freelancers = create_test_freelancers();
+    freelancers.push(Freelancer { job_success_rate: 85.0, ..freelancers[0].clone() });
+    let error = perform_regression(&freelancers).err().unwrap();
+    assert_eq!(error.to_string(), "insufficient data: 3 usable rows for 3 features; need at least 4");
+    assert!(perform_standardized_regression(&freelancers).is_err());
+}
+
+/// Tests that standardized columns are z-scores and predictions match the raw model
+#[test]
+fn test_standardized_regression() {
+    let freelancers = create_identifiable_freelancers();
+    let (x, _) = build_feature_matrix(&freelancers).unwrap();
+    let (standardized, scaler) = perform_standardized_regression(&freelancers).unwrap();
+    
+    let z = scaler.transform(&x);
+    for column in z.columns() {
+        assert!(column.mean().unwrap().abs() < 1e-9);
+        assert!((column.std(0.0) - 1.0).abs() < 1e-9);
+    }
+    let restored = scaler.inverse_transform(&z);
+    assert!(restored.iter().zip(x.iter()).all(|(a, b)| (a - b).abs() < 1e-9));
+    
+    let plain = perform_regression(&freelancers).unwrap();
+    let expected = plain.predict_batch(&x);
+    let from_standardized = standardized.predict_batch(&z);
+    let unstandardized = scaler.unstandardize(&standardized);
+    for ((e, s), u) in expected.iter().zip(from_standardized.iter()).zip(unstandardized.predict_batch(&x).iter()) {
+        assert!((e - s).abs() < 1e-6);
+        assert!((e - u).abs() < 1e-6);
+    }
+}
+
+/// Tests that ridge with no penalty matches least squares and a large penalty shrinks coefficients
+#[test]
+fn test_ridge_regression() {
+    let freelancers = create_identifiable_freelancers();
+    
+    let plain = perform_regression(&freelancers).unwrap();
+    let unpenalized = perform_ridge_regression(&freelancers, 0.0).unwrap();
+    for (a, b) in plain.coefficients.iter().zip(unpenalized.coefficients.iter()) {
+        assert!((a - b).abs() < 1e-6);
+    }
+    
+    let norm = |model: &RateModel| model.coefficients.dot(&model.coefficients).sqrt();
+    let ridge = perform_ridge_regression(&freelancers, 1000.0).unwrap();
+    assert!(norm(&ridge) < norm(&plain));
+    assert!(perform_ridge_regression(&freelancers, -1.0).is_err());
+}
+
+/// Tests that location regression adds platform and region columns with reference levels
+#[test]
+fn test_location_regression() {
+    let mut freelancers = Vec::new();
+    for i in 0..16 {
+        let category = ["Design", "Writing"][i % 2];
+        let experience = ["Expert", "Intermediate"][(i / 2) % 2];
+        let platform = ["Fiverr", "Upwork"][(i / 4) % 2];
+        let region = ["Asia", "Europe"][(i / 8) % 2];
+        // Additive rates: Writing +10, Intermediate -20, Upwork +5, Europe -3
+        let rate = 60.0 + 10.0 * (i % 2) as f32 - 20.0 * ((i / 2) % 2) as f32
+            + 5.0 * ((i / 4) % 2) as f32 - 3.0 * ((i / 8) % 2) as f32;
+        freelancers.push(Freelancer {
+            job_category: category.to_string(),
+            experience_level: experience.to_string(),
+            platform: platform.to_string(),
+            client_region: region.to_string(),
+            job_success_rate: 60.0 + (i * 7 % 40) as f32,
+            hourly_rate: rate,
+            ..Default::default()
+        });
+    }
+    
+    let (model, encoder) = perform_location_regression(&freelancers).unwrap();
+    assert_eq!(model.coefficients.len(), 1 + 2 + 2 + 2 + 2);
+    assert_eq!(model.feature_names[6], "Platform = Upwork");
+    assert_eq!(model.feature_names[8], "Client Region = Europe");
+    assert_eq!(model.coefficients[5], 0.0);
+    assert!((model.coefficients[6] - 5.0).abs() < 1e-6);
+    assert!((model.coefficients[8] + 3.0).abs() < 1e-6);
+    
+    let unknown = Freelancer { platform: "Toptal".to_string(), ..freelancers[0].clone() };
+    assert_eq!(&encoder.encode(&unknown)[5..7], &[0.0, 0.0]);
+    
+    let (plain, _) = perform_one_hot_regression(&freelancers).unwrap();
+    assert_eq!(plain.coefficients.len(), 1 + 2 + 2);
+}
+
+/// Tests that a saved and reloaded model predicts exactly like the original
+#[test]
+fn test_rate_model_save_load() {
+    let freelancers = create_identifiable_freelancers();
+    let model = perform_regression(&freelancers).unwrap();
+    let path = std::env::temp_dir().join(format!("part2_rate_model_{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+    
+    model.save(path).unwrap();
+    let loaded = RateModel::load(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    
+    assert_eq!(loaded.feature_names, model.feature_names);
+    assert_eq!(loaded.intercept, model.intercept);
+    let (x, _) = build_feature_matrix(&freelancers).unwrap();
+    assert_eq!(loaded.predict_batch(&x), model.predict_batch(&x));
+}
+
+/// Tests perfect positive and negative correlation and the zero-variance case
+#[test]
+fn test_pearson_correlation() {
+    let x = [1.0, 2.0, 3.0, 4.0];
+    assert!((pearson_correlation(&x, &[3.0, 5.0, 7.0, 9.0]).unwrap() - 1.0).abs() < 1e-12);
+    assert!((pearson_correlation(&x, &[8.0, 6.0, 4.0, 2.0]).unwrap() + 1.0).abs() < 1e-12);
+    assert_eq!(pearson_correlation(&x, &[5.0; 4]), None);
+    assert_eq!(pearson_correlation(&x, &[1.0]), None);
+    
+    let freelancers: Vec<Freelancer> = [(70.0, 20.0), (80.0, 30.0), (90.0, 40.0)].iter()
+        .map(|&(success, rate)| Freelancer {
+            job_success_rate: success,
+            earnings_usd: 1000.0,
+            hourly_rate: rate,
+            ..Default::default()
+        })
+        .collect();
+    let correlations = rate_correlations(&freelancers);
+    assert_eq!(correlations[0].0, "Job Success Rate");
+    assert!((correlations[0].1.unwrap() - 1.0).abs() < 1e-12);
+    assert_eq!(correlations[1], ("Earnings USD".to_string(), None));
+}
+
+/// Tests that a blank rate does not poison the target encoding's global or category means
+#[test]
+fn test_target_encode_skips_missing_rates() {
+    let freelancers: Vec<Freelancer> = [("Design", 20.0), ("Design", f32::NAN), ("Writing", 40.0), ("Data Science", f32::NAN)].iter()
+        .map(|&(category, rate)| FreelancerBuilder::new().job_category(category).hourly_rate(rate).build())
+        .collect();
+    
+    // Global mean over the two known rates is 30
+    let encoding = target_encode(&freelancers, |f| f.job_category.clone());
+    let expected = (20.0 + TARGET_ENCODING_SMOOTHING * 30.0) / (1.0 + TARGET_ENCODING_SMOOTHING);
+    assert!((encoding["Design"] - expected).abs() < 1e-9);
+    assert!(!encoding.contains_key("Data Science"));
+}
+
+/// Tests that the target-encoded regression trains only on freelancers with a known rate
+#[test]
+fn test_target_encoded_regression_skips_missing_rates() {
+    let rows = [
+        ("Design", "Entry Level", 70.0, 20.0),
+        ("Design", "Intermediate", 80.0, 30.0),
+        ("Web Development", "Expert", 90.0, 50.0),
+        ("Web Development", "Intermediate", 85.0, f32::NAN),
+        ("Web Development", "Intermediate", 75.0, 60.0),
+        ("Writing", "Entry Level", 95.0, 35.0),
+        ("Writing", "Expert", 60.0, 45.0),
+    ];
+    let freelancers: Vec<Freelancer> = rows.iter()
+        .map(|&(category, level, success, rate)| {
+            FreelancerBuilder::new()
+                .job_category(category)
+                .experience_level(level)
+                .job_success_rate(success)
+                .hourly_rate(rate)
+                .build()
+        })
+        .collect();
+    
+    let model = perform_target_encoded_regression(&freelancers).unwrap();
+    assert_eq!(model.global_mean, 40.0);
+    assert!(model.model.intercept.is_finite());
+    assert!(model.model.coefficients.iter().all(|c| c.is_finite()));
+}
+
+/// Tests that test-set rows with a blank rate are not scored by compare_target_transforms
+#[test]
+fn test_compare_target_transforms_skips_missing_rates() {
+    let freelancers = create_comparison_freelancers(|i| i % 2 == 0);
+    let (_, test) = train_test_split(&freelancers, COMPARISON_TEST_FRACTION, 42);
+    assert!(test.iter().any(|f| f.hourly_rate.is_nan()));
+    
+    let results = compare_target_transforms(&freelancers, 42).unwrap();
+    assert!(results.iter().all(|(_, rmse)| rmse.is_finite()));
+}
@@ -0,0 +1,270 @@
+//! Module for condensed overviews of an analysis run.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use plotters::prelude::*;
+use crate::data_loader::Freelancer;
+use crate::error_analysis::{compute_error_metrics, plot_predicted_vs_actual, ErrorMetrics};
+use crate::regression::{encode_features, RateModel};
+
+/// Writes a one-screen overview of a run.
+/// 
+/// Reports the dataset size, the number of clusters, the share of freelancers in
+/// the largest (giant) cluster, the model's R² and the feature with the largest
+/// absolute coefficient.
+/// 
+/// # Arguments
+/// `out` - Destination for the summary
+/// `freelancers` - Freelancers in the run
+/// `clusters` - Clusters of freelancer indices, e.g. connected components of the collaboration graph
+/// `model` - Fitted rate model
+/// `metrics` - Error metrics of the model
+/// 
+/// # Returns: `io::Result<()>` - Success or write error
+pub fn write_run_summary<W: Write>(
+    out: &mut W,
+    freelancers: &[Freelancer],
+    clusters: &[Vec<usize>],
+    model: &RateModel,
+    metrics: &ErrorMetrics,
+) -> io::Result<()> {
+    let largest = clusters.iter().map(|c| c.len()).max().unwrap_or(0);
+    let giant_fraction = if freelancers.is_empty() { 0.0 } else { largest as f64 / freelancers.len() as f64 };
+    
+    let top_feature = model.feature_names.iter()
+        .zip(model.coefficients.iter())
+        .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()));
+    
+    writeln!(out, "Run Summary")?;
+    writeln!(out, "- Freelancers: {}", freelancers.len())?;
+    writeln!(out, "- Clusters: {}", clusters.len())?;
+    writeln!(out, "- Giant component: {:.1}% of freelancers", giant_fraction * 100.0)?;
+    writeln!(out, "- Regression R²: {:.4}", metrics.r_squared)?;
+    match top_feature {
+        Some((name, coefficient)) => writeln!(out, "- Top feature: {} (coefficient {:.2})", name, coefficient)?,
+        None => writeln!(out, "- Top feature: none")?,
+    }
+    Ok(())
+}
+
+/// Prints the run overview produced by `write_run_summary` to stdout.
+/// 
+/// # Arguments: `freelancers` - Freelancers in the run, `clusters` - Clusters of freelancer indices,
+/// `model` - Fitted rate model, `metrics` - Error metrics of the model
+/// 
+/// # Returns: `io::Result<()>` - Success or write error
+pub fn print_run_summary(
+    freelancers: &[Freelancer],
+    clusters: &[Vec<usize>],
+    model: &RateModel,
+    metrics: &ErrorMetrics,
+) -> io::Result<()> {
+    write_run_summary(&mut io::stdout().lock(), freelancers, clusters, model, metrics)
+}
+
+/// File name of the predicted vs actual chart written by `write_html_report`.
+pub const PREDICTED_VS_ACTUAL_CHART: &str = "predicted_vs_actual.png";
+
+/// File name of the per-cluster mean rate chart written by `write_html_report`.
+pub const CLUSTER_RATES_CHART: &str = "cluster_mean_rates.png";
+
+/// Writes a self-contained HTML report of a run into `out_dir`.
+/// 
+/// Generates the predicted vs actual and per-cluster mean rate charts as PNGs, then
+/// writes `index.html` with the run summary, the regression coefficients and error
+/// metrics, a table per cluster and the two charts. Freelancers without a known rate
+/// are left out of the metrics and the predicted vs actual chart.
+/// 
+/// # Arguments
+/// `freelancers` - Freelancers in the run
+/// `clusters` - Clusters of freelancer indices
+/// `model` - Fitted rate model
+/// `out_dir` - Directory for `index.html` and the charts, created if missing
+/// 
+/// # Returns: `Result<(), Box<dyn Error>>` - Success or error writing the files
+pub fn write_html_report(
+    freelancers: &[Freelancer],
+    clusters: &[Vec<usize>],
+    model: &RateModel,
+    out_dir: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = Path::new(out_dir);
+    fs::create_dir_all(dir)?;
+    
+    let (actual, predicted): (Vec<f64>, Vec<f64>) = freelancers.iter()
+        .filter(|f| f.hourly_rate.is_finite())
+        .map(|f| (f.hourly_rate as f64, model.predict(&encode_features(f))))
+        .unzip();
+    let metrics = compute_error_metrics(&actual, &predicted);
+    plot_predicted_vs_actual(&actual, &predicted, &dir.join(PREDICTED_VS_ACTUAL_CHART).to_string_lossy())?;
+    plot_cluster_mean_rates(freelancers, clusters, &dir.join(CLUSTER_RATES_CHART).to_string_lossy())?;
+    
+    let mut summary = Vec::new();
+    write_run_summary(&mut summary, freelancers, clusters, model, &metrics)?;
+    
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Freelancer Analysis Report</title>\n");
+    html.push_str("<style>body { font-family: sans-serif; } table { border-collapse: collapse; margin-bottom: 1em; } ");
+    html.push_str("td, th { border: 1px solid #ccc; padding: 4px 8px; text-align: left; }</style>\n</head>\n<body>\n");
+    html.push_str("<h1>Freelancer Analysis Report</h1>\n");
+    html.push_str("<p><a href=\"#regression\">Regression</a> | <a href=\"#clusters\">Clusters</a> | <a href=\"#charts\">Charts</a></p>\n");
+    html.push_str(&format!("<pre>{}</pre>\n", escape_html(&String::from_utf8_lossy(&summary))));
+    
+    html.push_str("<h2 id=\"regression\">Regression</h2>\n<table>\n<tr><th>Term</th><th>Coefficient</th></tr>\n");
+    html.push_str(&format!("<tr><td>Intercept</td><td>{:.4}</td></tr>\n", model.intercept));
+    for (name, coefficient) in model.feature_names.iter().zip(model.coefficients.iter()) {
+        html.push_str(&format!("<tr><td>{}</td><td>{:.4}</td></tr>\n", escape_html(name), coefficient));
+    }
+    html.push_str("</table>\n<table>\n<tr><th>Metric</th><th>Value</th></tr>\n");
+    for (name, value) in [("MSE", metrics.mse), ("RMSE", metrics.rmse), ("MAE", metrics.mae), ("R²", metrics.r_squared)] {
+        html.push_str(&format!("<tr><td>{}</td><td>{:.4}</td></tr>\n", name, value));
+    }
+    html.push_str("</table>\n");
+    
+    html.push_str("<h2 id=\"clusters\">Clusters</h2>\n");
+    for (i, members) in clusters.iter().enumerate() {
+        html.push_str(&format!("<h3>Cluster {} ({} members)</h3>\n", i + 1, members.len()));
+        html.push_str("<table>\n<tr><th>Attribute</th><th>Value</th></tr>\n");
+        html.push_str(&format!("<tr><td>Average Hourly Rate</td><td>${:.2}</td></tr>\n", mean_of(members, freelancers, |f| f.hourly_rate)));
+        html.push_str(&format!("<tr><td>Average Earnings</td><td>${:.2}</td></tr>\n", mean_of(members, freelancers, |f| f.earnings_usd)));
+        html.push_str(&format!("<tr><td>Average Job Success Rate</td><td>{:.1}%</td></tr>\n", mean_of(members, freelancers, |f| f.job_success_rate)));
+        for (label, field) in [
+            ("Dominant Category", (|f: &Freelancer| f.job_category.as_str()) as fn(&Freelancer) -> &str),
+            ("Dominant Platform", |f| f.platform.as_str()),
+            ("Dominant Experience", |f| f.experience_level.as_str()),
+        ] {
+            html.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>\n", label, escape_html(dominant_value(members, freelancers, field))));
+        }
+        html.push_str("</table>\n");
+    }
+    
+    html.push_str("<h2 id=\"charts\">Charts</h2>\n");
+    for (chart, alt) in [(PREDICTED_VS_ACTUAL_CHART, "Predicted vs actual hourly rate"), (CLUSTER_RATES_CHART, "Mean hourly rate per cluster")] {
+        html.push_str(&format!("<p><a href=\"{0}\"><img src=\"{0}\" alt=\"{1}\" width=\"600\"></a></p>\n", chart, alt));
+    }
+    html.push_str("</body>\n</html>\n");
+    
+    fs::write(dir.join("index.html"), html)?;
+    Ok(())
+}
+
+/// Plots the mean hourly rate of each cluster as a bar chart.
+fn plot_cluster_mean_rates(freelancers: &[Freelancer], clusters: &[Vec<usize>], path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let means: Vec<f64> = clusters.iter()
+        .map(|members| mean_of(members, freelancers, |f| f.hourly_rate))
+        .map(|mean| if mean.is_finite() { mean } else { 0.0 })
+        .collect();
+    let max_rate = means.iter().copied().fold(0.0, f64::max).max(1.0) * 1.1;
+    
+    let root = BitMapBackend::new(path, (800, 600)).into_drawing_area();
+    root.fill(&WHITE)?;
+    
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Mean Hourly Rate per Cluster", ("sans-serif", 30))
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d((1..means.len().max(1)).into_segmented(), 0.0..max_rate)?;
+    
+    chart.configure_mesh()
+        .x_desc("Cluster")
+        .y_desc("Mean Hourly Rate (USD)")
+        .bold_line_style(BLACK.mix(0.2))
+        .draw()?;
+    
+    chart.draw_series(
+        Histogram::vertical(&chart)
+            .style(BLUE.mix(0.6).filled())
+            .margin(10)
+            .data(means.iter().enumerate().map(|(i, &mean)| (i + 1, mean))),
+    )?;
+    
+    root.present()?;
+    Ok(())
+}
+
+/// Averages a numeric field over the finite values of a cluster's members.
+fn mean_of(members: &[usize], freelancers: &[Freelancer], field: fn(&Freelancer) -> f32) -> f64 {
+    let values: Vec<f64> = members.iter()
+        .map(|&i| field(&freelancers[i]) as f64)
+        .filter(|v| v.is_finite())
+        .collect();
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Finds the most common value of a text field among a cluster's members.
+fn dominant_value<'a>(members: &[usize], freelancers: &'a [Freelancer], field: fn(&Freelancer) -> &str) -> &'a str {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for &i in members {
+        *counts.entry(field(&freelancers[i])).or_insert(0) += 1;
+    }
+    counts.into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+        .map(|(value, _)| value)
+        .unwrap_or("")
+}
+
+/// Escapes the characters with special meaning in HTML text.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Tests that the summary contains the dataset size, clusters, giant component, R² and top feature
+#[test]
+fn test_write_run_summary() {
+    let freelancers = vec![Freelancer::default(); 4];
+    let clusters = vec![vec![0, 1, 2], vec![3]];
+    let model = RateModel {
+        coefficients: ndarray::Array1::from_vec(vec![2.0, -7.5, 3.0]),
+        intercept: 10.0,
+        feature_names: vec!["Job Success Rate".to_string(), "Job Category".to_string(), "Experience Level".to_string()],
+    };
+    let metrics = ErrorMetrics { mse: 4.0, rmse: 2.0, mae: 1.5, r_squared: 0.8125 };
+    
+    let mut buffer = Vec::new();
+    write_run_summary(&mut buffer, &freelancers, &clusters, &model, &metrics).unwrap();
+    let summary = String::from_utf8(buffer).unwrap();
+    
+    assert!(summary.contains("Freelancers: 4"));
+    assert!(summary.contains("Clusters: 2"));
+    assert!(summary.contains("Giant component: 75.0%"));
+    assert!(summary.contains("R²: 0.8125"));
+    assert!(summary.contains("Top feature: Job Category"));
+}
+
+/// Tests that the HTML report and the charts it references are written
+#[test]
+fn test_write_html_report() {
+    let freelancers: Vec<Freelancer> = [(20.0, "Design"), (40.0, "Web Development"), (35.0, "Web Development")]
+        .iter()
+        .enumerate()
+        .map(|(i, &(rate, category))| Freelancer {
+            id: i as u32,
+            job_category: category.to_string(),
+            experience_level: "Expert".to_string(),
+            hourly_rate: rate,
+            job_success_rate: 80.0 + i as f32,
+            ..Default::default()
+        })
+        .collect();
+    let clusters = vec![vec![0], vec![1, 2]];
+    let model = RateModel {
+        coefficients: ndarray::Array1::from_vec(vec![10.0, 4.0, 2.0]),
+        intercept: 5.0,
+        feature_names: vec!["Job Success Rate".to_string(), "Job Category".to_string(), "Experience Level".to_string()],
+    };
+    
+    let out_dir = std::env::temp_dir().join(format!("part2_html_report_{}", std::process::id()));
+    write_html_report(&freelancers, &clusters, &model, &out_dir.to_string_lossy()).unwrap();
+    
+    let html = fs::read_to_string(out_dir.join("index.html")).unwrap();
+    for chart in [PREDICTED_VS_ACTUAL_CHART, CLUSTER_RATES_CHART] {
+        assert!(html.contains(&format!("src=\"{}\"", chart)));
+        assert!(out_dir.join(chart).exists());
+    }
+    assert!(html.contains("Cluster 2 (2 members)"));
+    assert!(html.contains("<td>Dominant Category</td><td>Web Development</td>"));
+    fs::remove_dir_all(&out_dir).unwrap();
+}